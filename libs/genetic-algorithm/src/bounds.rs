@@ -0,0 +1,150 @@
+use crate::*;
+
+// What happens to a gene that ends up outside of its bounds
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoundPolicy {
+    // Moves it onto the nearest bound
+    #[default]
+    Clamp,
+    // Mirrors it back into the range, as if it bounced off the bound
+    Reflect,
+    // Brings it in from the other side of the range
+    Wrap,
+    // Replaces it with a random value from within the range
+    Resample,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Ranges {
+    Global(f32, f32),
+    PerGene(Vec<(f32, f32)>),
+}
+
+// Valid ranges of a chromosome's numeric genes - either one range shared
+// by all genes or a separate range for each of them.
+//
+// Bounds are attached with `Chromosome::with_bounds()`; from then on they
+// are passed down to the children and enforced by every crossover and
+// mutation operator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounds {
+    ranges: Ranges,
+    policy: BoundPolicy,
+}
+
+impl Bounds {
+    pub fn global(min: f32, max: f32, policy: BoundPolicy) -> Self {
+        assert!(min <= max);
+
+        Self {
+            ranges: Ranges::Global(min, max),
+            policy,
+        }
+    }
+
+    // Genes past the end of `ranges` are left unbounded
+    pub fn per_gene(ranges: Vec<(f32, f32)>, policy: BoundPolicy) -> Self {
+        assert!(ranges.iter().all(|(min, max)| min <= max));
+
+        Self {
+            ranges: Ranges::PerGene(ranges),
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> BoundPolicy {
+        self.policy
+    }
+
+    pub fn range(&self, locus: usize) -> Option<(f32, f32)> {
+        match &self.ranges {
+            Ranges::Global(min, max) => Some((*min, *max)),
+            Ranges::PerGene(ranges) => ranges.get(locus).copied(),
+        }
+    }
+
+    // Brings `value` back within `min..=max`; returns `None` if it already
+    // was there
+    pub fn constrain(&self, rng: &mut dyn RngCore, value: f32, min: f32, max: f32) -> Option<f32> {
+        if (min..=max).contains(&value) {
+            return None;
+        }
+
+        let span = max - min;
+
+        // Reflecting or wrapping makes no sense for these
+        if span == 0.0 || !value.is_finite() {
+            return Some(match self.policy {
+                BoundPolicy::Resample => rng.gen_range(min..=max),
+                _ if value.is_nan() => min,
+                _ => value.clamp(min, max),
+            });
+        }
+
+        Some(match self.policy {
+            BoundPolicy::Clamp => value.clamp(min, max),
+            BoundPolicy::Reflect => {
+                let offset = (value - min).rem_euclid(2.0 * span);
+
+                if offset > span {
+                    max - (offset - span)
+                } else {
+                    min + offset
+                }
+            }
+            BoundPolicy::Wrap => min + (value - min).rem_euclid(span),
+            BoundPolicy::Resample => rng.gen_range(min..=max),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn constrain(policy: BoundPolicy, value: f32) -> Option<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        Bounds::global(-1.0, 1.0, policy).constrain(&mut rng, value, -1.0, 1.0)
+    }
+
+    #[test]
+    fn policies() {
+        assert_eq!(constrain(BoundPolicy::Clamp, 0.5), None);
+        assert_eq!(constrain(BoundPolicy::Clamp, 1.5), Some(1.0));
+        assert_eq!(constrain(BoundPolicy::Clamp, -3.0), Some(-1.0));
+
+        assert_eq!(constrain(BoundPolicy::Reflect, 1.5), Some(0.5));
+        assert_eq!(constrain(BoundPolicy::Reflect, -1.25), Some(-0.75));
+        assert_eq!(constrain(BoundPolicy::Reflect, 3.5), Some(-0.5));
+
+        assert_eq!(constrain(BoundPolicy::Wrap, 1.5), Some(-0.5));
+        assert_eq!(constrain(BoundPolicy::Wrap, -1.25), Some(0.75));
+
+        let resampled = constrain(BoundPolicy::Resample, 7.0).unwrap();
+        assert!((-1.0..=1.0).contains(&resampled));
+
+        assert_eq!(constrain(BoundPolicy::Reflect, f32::INFINITY), Some(1.0));
+        assert_eq!(constrain(BoundPolicy::Wrap, f32::NAN), Some(-1.0));
+    }
+
+    #[test]
+    fn enforced_by_operators() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let bounds = Bounds::per_gene(vec![(0.0, 1.0), (-2.0, 2.0)], BoundPolicy::Clamp);
+
+        let parent_a: Chromosome = vec![0.5, 1.5, 100.0].into_iter().collect();
+        let parent_a = parent_a.with_bounds(bounds);
+        let parent_b: Chromosome = vec![0.5, -1.5, 100.0].into_iter().collect();
+
+        let mut child = UniformCrossover.crossover(&mut rng, &parent_a, &parent_b);
+        assert_eq!(child.bounds(), parent_a.bounds());
+
+        GaussianMutation::new(1.0, 10.0).mutate(&mut rng, &mut child);
+
+        assert!((0.0..=1.0).contains(&child[0]));
+        assert!((-2.0..=2.0).contains(&child[1]));
+        assert!(child.bound_hits() > 0);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+impl<S, C, M> GeneticAlgorithm<S, C, M>
+where
+    S: SelectionMethod,
+{
+    // Breeds a single child, bypassing selection - for when individuals
+    // reproduce on their own schedule instead of all at once, as in
+    // `evolve()`.
+    //
+    // Without a mate, the child is a mutated copy of `parent`.
+    pub fn breed<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        parent: &I,
+        mate: Option<&I>,
+        genealogy: Option<&mut Genealogy>,
+    ) -> I
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        let mate_or_parent = mate.unwrap_or(parent);
+
+        let mut child =
+            self.crossover_method
+                .crossover(rng, parent.chromosome(), mate_or_parent.chromosome());
+
+        let Some(genealogy) = genealogy else {
+            self.mutation_method.mutate(rng, &mut child);
+            return I::create(child);
+        };
+
+        let crossed_over = child.clone();
+        self.mutation_method.mutate(rng, &mut child);
+
+        let mut parents: Vec<_> = [parent, mate_or_parent]
+            .iter()
+            .filter_map(|parent| parent.lineage())
+            .map(|lineage| lineage.id)
+            .collect();
+
+        parents.dedup();
+
+        let mutation = MutationRecord::between(&crossed_over, &child);
+        let mut child = I::create(child);
+        child.set_lineage(genealogy.child(parents, mutation));
+        child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect())
+    }
+
+    #[test]
+    fn without_mate() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.0, 0.0),
+        );
+
+        let parent = individual(&[1.0, 2.0, 3.0]);
+        let child = ga.breed(&mut rng, &parent, None, None);
+
+        assert_eq!(child.chromosome(), parent.chromosome());
+    }
+
+    #[test]
+    fn with_mate() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.1),
+        );
+
+        let parent = individual(&[0.0; 8]);
+        let mate = individual(&[10.0; 8]);
+        let mut genealogy = Genealogy::new();
+
+        let child = ga.breed(&mut rng, &parent, Some(&mate), Some(&mut genealogy));
+
+        assert!(child.chromosome().iter().any(|gene| *gene < 5.0));
+        assert!(child.chromosome().iter().any(|gene| *gene > 5.0));
+        assert_eq!(genealogy.len(), 1);
+    }
+}
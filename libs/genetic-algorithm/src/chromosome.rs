@@ -1,23 +1,95 @@
+use crate::*;
 use std::ops::Index;
+use std::sync::Arc;
 
+// Genes are `f32`s by default, but any `Gene` will do - see `gene.rs`
 #[derive(Clone, Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
+    bounds: Option<Arc<Bounds>>,
+    // How many genes had to be brought back within bounds while breeding
+    // this chromosome
+    bound_hits: usize,
 }
 
-impl Chromosome {
+impl<G> Chromosome<G> {
     pub fn len(&self) -> usize {
         self.genes.len()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
 
+    // Accepts either `Bounds` or `Arc<Bounds>`, the latter allowing for
+    // the whole population to share one instance
+    pub fn with_bounds(mut self, bounds: impl Into<Arc<Bounds>>) -> Self {
+        self.bounds = Some(bounds.into());
+        self
+    }
+
+    pub fn bounds(&self) -> Option<&Bounds> {
+        self.bounds.as_deref()
+    }
+
+    pub fn bound_hits(&self) -> usize {
+        self.bound_hits
+    }
+
+    // Crossover operators build their children from scratch - this is how
+    // the children get to keep their parents' bounds
+    pub fn inherit_bounds(&mut self, parent: &Self) {
+        self.bounds = parent.bounds.clone();
+    }
+}
+
+impl<G> Chromosome<G>
+where
+    G: Gene,
+{
+    // Euclidean distance between two genotypes
+    pub fn distance(&self, other: &Self) -> f32 {
+        assert_eq!(self.len(), other.len());
+
+        self.genes
+            .iter()
+            .zip(&other.genes)
+            .map(|(a, b)| a.distance(b).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    // Brings all genes back within bounds (if there are any); returns how
+    // many of them were out of range
+    pub fn enforce_bounds(&mut self, rng: &mut dyn RngCore) -> usize {
+        let Some(bounds) = &self.bounds else {
+            return 0;
+        };
+
+        let mut hits = 0;
+
+        for (locus, gene) in self.genes.iter_mut().enumerate() {
+            if let Some((min, max)) = bounds.range(locus)
+                && gene.constrain(rng, bounds, min, max)
+            {
+                hits += 1;
+            }
+        }
+
+        self.bound_hits += hits;
+        hits
+    }
+}
+
+impl Chromosome {
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut out = Vec::with_capacity(self.genes.len() * 4);
         for &w in &self.genes {
@@ -25,6 +97,18 @@ impl Chromosome {
         }
         out
     }
+
+    // Inverse of `to_bytes()`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GenomeError> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(GenomeError::InvalidLength(bytes.len()));
+        }
+
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|gene| f32::from_le_bytes([gene[0], gene[1], gene[2], gene[3]]))
+            .collect())
+    }
 }
 
 // ---
@@ -33,8 +117,8 @@ impl Chromosome {
 // | e.g. if you implemented `Index<&str>`, you could write:
 // |   chromosome["yass"]
 // ------- v---v
-impl Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
@@ -47,20 +131,22 @@ impl Index<usize> for Chromosome {
 // |
 // | (sometimes it's called the type an iterator *yields*)
 // |
-// | intuitively, since our chromosome is built of of floating-point numbers, we
-// | expect floating-point numbers in here as well
-// -------------- v-v
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
+// | intuitively, since our chromosome is built of genes, we expect genes in
+// | here as well
+// ----------------- v
+impl<G> FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>>(iter: T) -> Self {
         Self {
             genes: iter.into_iter().collect(),
+            bounds: None,
+            bound_hits: 0,
         }
     }
 }
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = std::vec::IntoIter<f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = std::vec::IntoIter<G>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
@@ -73,3 +159,10 @@ impl PartialEq for Chromosome {
         approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice())
     }
 }
+
+#[cfg(test)]
+impl PartialEq for Chromosome<bool> {
+    fn eq(&self, other: &Self) -> bool {
+        self.genes == other.genes
+    }
+}
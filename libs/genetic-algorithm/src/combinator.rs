@@ -0,0 +1,275 @@
+use crate::*;
+
+// Operators built out of other operators, e.g.:
+//
+//   RandomChoice::<Box<dyn MutationMethod>>::new()
+//       .with(0.9, Box::new(GaussianMutation::new(0.1, 0.05)))
+//       .with(0.1, Box::new(ResetMutation::new(0.05, -1.0, 1.0)))
+//
+// Operators of a single type can be combined directly; mixing different
+// ones requires boxing them, as above.
+
+// Applies all mutations, one after another
+#[derive(Clone, Debug)]
+pub struct MutationChain<M> {
+    methods: Vec<M>,
+}
+
+impl<M> MutationChain<M> {
+    pub fn new() -> Self {
+        Self {
+            methods: Vec::new(),
+        }
+    }
+
+    pub fn then(mut self, method: M) -> Self {
+        self.methods.push(method);
+        self
+    }
+}
+
+impl<M> Default for MutationChain<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, M> MutationMethod<G> for MutationChain<M>
+where
+    M: MutationMethod<G>,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for method in &self.methods {
+            method.mutate(rng, child);
+        }
+    }
+}
+
+// Picks one of the operators each time it's used, with probability
+// proportional to its weight
+#[derive(Clone, Debug)]
+pub struct RandomChoice<O> {
+    options: Vec<(f32, O)>,
+}
+
+impl<O> RandomChoice<O> {
+    pub fn new() -> Self {
+        Self {
+            options: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, weight: f32, operator: O) -> Self {
+        assert!(weight >= 0.0);
+
+        self.options.push((weight, operator));
+        self
+    }
+
+    fn choose(&self, rng: &mut dyn RngCore) -> &O {
+        &self
+            .options
+            .choose_weighted(rng, |(weight, _)| *weight)
+            .expect("got no operators (or none with a positive weight)")
+            .1
+    }
+}
+
+impl<O> Default for RandomChoice<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, O> MutationMethod<G> for RandomChoice<O>
+where
+    O: MutationMethod<G>,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        self.choose(rng).mutate(rng, child);
+    }
+}
+
+impl<G, O> CrossoverMethod<G> for RandomChoice<O>
+where
+    O: CrossoverMethod<G>,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        self.choose(rng).crossover(rng, parent_a, parent_b)
+    }
+}
+
+// Uses the operator only every now and then; a skipped crossover yields a
+// copy of the first parent
+#[derive(Clone, Debug)]
+pub struct WithProbability<O> {
+    probability: f32,
+    operator: O,
+}
+
+impl<O> WithProbability<O> {
+    pub fn new(probability: f32, operator: O) -> Self {
+        assert!((0.0..=1.0).contains(&probability));
+
+        Self {
+            probability,
+            operator,
+        }
+    }
+}
+
+impl<G, O> MutationMethod<G> for WithProbability<O>
+where
+    O: MutationMethod<G>,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if rng.gen_bool(self.probability as _) {
+            self.operator.mutate(rng, child);
+        }
+    }
+}
+
+impl<G, O> CrossoverMethod<G> for WithProbability<O>
+where
+    G: Gene,
+    O: CrossoverMethod<G>,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        if rng.gen_bool(self.probability as _) {
+            return self.operator.crossover(rng, parent_a, parent_b);
+        }
+
+        let mut child: Chromosome<G> = parent_a.iter().cloned().collect();
+        child.inherit_bounds(parent_a);
+        child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    // Adds a constant to every gene, so that it's easy to tell which
+    // operators were applied
+    struct Shift(f32);
+
+    impl MutationMethod for Shift {
+        fn mutate(&self, _rng: &mut dyn RngCore, child: &mut Chromosome) {
+            for gene in child.iter_mut() {
+                *gene += self.0;
+            }
+        }
+    }
+
+    fn mutate(method: &dyn MutationMethod, rng: &mut dyn RngCore) -> f32 {
+        let mut child: Chromosome = vec![0.0].into_iter().collect();
+        method.mutate(rng, &mut child);
+        child[0]
+    }
+
+    #[test]
+    fn chain() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let chain = MutationChain::new().then(Shift(1.0)).then(Shift(10.0));
+
+        assert_eq!(mutate(&chain, &mut rng), 11.0);
+    }
+
+    #[test]
+    fn random_choice() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let choice = RandomChoice::new()
+            .with(9.0, Shift(1.0))
+            .with(1.0, Shift(100.0))
+            .with(0.0, Shift(1000.0));
+
+        let outcomes: Vec<_> = (0..1000).map(|_| mutate(&choice, &mut rng)).collect();
+        let rare = outcomes.iter().filter(|&&gene| gene == 100.0).count();
+
+        assert!(outcomes.iter().all(|&gene| gene == 1.0 || gene == 100.0));
+        assert!((60..140).contains(&rare), "{rare}");
+    }
+
+    #[test]
+    fn with_probability() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        assert_eq!(
+            mutate(&WithProbability::new(0.0, Shift(1.0)), &mut rng),
+            0.0
+        );
+        assert_eq!(
+            mutate(&WithProbability::new(1.0, Shift(1.0)), &mut rng),
+            1.0
+        );
+
+        let parent_a: Chromosome = vec![1.0, 1.0].into_iter().collect();
+        let parent_b: Chromosome = vec![2.0, 2.0].into_iter().collect();
+
+        let child =
+            WithProbability::new(0.0, UniformCrossover).crossover(&mut rng, &parent_a, &parent_b);
+
+        assert_eq!(child, parent_a);
+    }
+
+    #[test]
+    fn mixed_operators() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            MutationChain::<Box<dyn MutationMethod>>::new()
+                .then(Box::new(GaussianMutation::new(0.5, 0.1)))
+                .then(Box::new(WithProbability::new(
+                    0.1,
+                    ResetMutation::new(0.5, 0.0, 5.0),
+                ))),
+        );
+
+        // Parents' genes are all far outside of the reset range, so every
+        // child's gene tells which operators touched it
+        let population: Vec<_> = (1..=50)
+            .map(|n| TestIndividual::create(vec![10.0 * n as f32; 10].into_iter().collect()))
+            .collect();
+
+        let (children, _) = ga.evolve(&mut rng, &population);
+        assert_eq!(children.len(), 50);
+
+        let genes: Vec<f32> = children
+            .iter()
+            .flat_map(|child| child.chromosome().iter().copied())
+            .collect();
+
+        let (reset, inherited): (Vec<f32>, Vec<f32>) =
+            genes.iter().partition(|gene| (0.0..5.0).contains(*gene));
+
+        let nudged = inherited
+            .iter()
+            .filter(|gene| {
+                let offset = (*gene - (*gene / 10.0).round() * 10.0).abs();
+                offset > 0.0 && offset <= 0.1
+            })
+            .count();
+
+        let untouched = inherited.iter().filter(|gene| *gene % 10.0 == 0.0).count();
+
+        assert_eq!(nudged + untouched, inherited.len());
+        assert!(!reset.is_empty());
+        assert!(nudged > 0);
+        assert!(untouched > 0);
+    }
+}
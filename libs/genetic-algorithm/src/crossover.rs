@@ -3,11 +3,27 @@ mod uniform;
 pub use self::uniform::*;
 use crate::*;
 
-pub trait CrossoverMethod {
+// Implementations should hand their parents' bounds down to the child -
+// see `Chromosome::inherit_bounds()` and `Chromosome::enforce_bounds()`
+pub trait CrossoverMethod<G = f32> {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
+}
+
+impl<G, C> CrossoverMethod<G> for Box<C>
+where
+    C: CrossoverMethod<G> + ?Sized,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        (**self).crossover(rng, parent_a, parent_b)
+    }
 }
@@ -3,20 +3,28 @@ use crate::*;
 #[derive(Clone, Debug)]
 pub struct UniformCrossover;
 
-impl CrossoverMethod for UniformCrossover {
+impl<G> CrossoverMethod<G> for UniformCrossover
+where
+    G: Gene,
+{
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome {
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
         assert_eq!(parent_a.len(), parent_b.len());
 
-        parent_a
+        let mut child: Chromosome<G> = parent_a
             .iter()
             .zip(parent_b.iter())
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
-            .collect()
+            .map(|(a, b)| if rng.gen_bool(0.5) { a } else { b })
+            .cloned()
+            .collect();
+
+        child.inherit_bounds(parent_a);
+        child.enforce_bounds(rng);
+        child
     }
 }
 
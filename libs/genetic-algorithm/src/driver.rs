@@ -0,0 +1,374 @@
+use crate::*;
+use std::time::{Duration, Instant};
+
+// Individual used by `GeneticAlgorithm::run()`, whose fitness comes from the
+// evaluation closure
+#[derive(Clone, Debug)]
+pub struct Evaluated<G = f32> {
+    pub chromosome: Chromosome<G>,
+    pub fitness: f32,
+}
+
+impl<G> Individual<G> for Evaluated<G> {
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self {
+            chromosome,
+            fitness: 0.0,
+        }
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        &self.chromosome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+}
+
+// When to stop `GeneticAlgorithm::run()` - whichever condition is met first
+// wins; at least one of them has to be set
+#[derive(Clone, Debug, Default)]
+pub struct StopConditions {
+    max_generations: Option<usize>,
+    target_fitness: Option<f32>,
+    stagnation: Option<usize>,
+    time_budget: Option<Duration>,
+}
+
+impl StopConditions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_generations(mut self, generations: usize) -> Self {
+        self.max_generations = Some(generations);
+        self
+    }
+
+    // Stops as soon as any individual reaches given fitness
+    pub fn target_fitness(mut self, fitness: f32) -> Self {
+        self.target_fitness = Some(fitness);
+        self
+    }
+
+    // Stops when the best fitness hasn't improved for given number of
+    // generations
+    pub fn stagnation(mut self, generations: usize) -> Self {
+        assert!(generations > 0);
+
+        self.stagnation = Some(generations);
+        self
+    }
+
+    pub fn time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.max_generations.is_none()
+            && self.target_fitness.is_none()
+            && self.stagnation.is_none()
+            && self.time_budget.is_none()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    MaxGenerations,
+    TargetFitness,
+    Stagnation,
+    TimeBudget,
+}
+
+pub trait Observer {
+    // Called for every evaluated generation, starting with the initial one
+    // (generation 0)
+    fn observe(&mut self, generation: usize, statistics: &Statistics);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(usize, &Statistics),
+{
+    fn observe(&mut self, generation: usize, statistics: &Statistics) {
+        self(generation, statistics)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RunOutcome<G = f32> {
+    pub population: Vec<Evaluated<G>>,
+    // Fittest individual seen across all generations
+    pub best: Evaluated<G>,
+    // How many times the population has been evolved
+    pub generations: usize,
+    pub reason: StopReason,
+    pub statistics: Statistics,
+}
+
+impl<S, C, M> GeneticAlgorithm<S, C, M>
+where
+    S: SelectionMethod,
+{
+    // Evolves `population` until one of `stop` conditions is met, scoring
+    // each chromosome with `evaluate`.
+    //
+    // Note that e.g. `RouletteWheelSelection` requires fitness to be
+    // non-negative.
+    pub fn run<G, F>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: Vec<Chromosome<G>>,
+        mut evaluate: F,
+        stop: &StopConditions,
+        observer: &mut dyn Observer,
+    ) -> RunOutcome<G>
+    where
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+        F: FnMut(&Chromosome<G>) -> f32,
+    {
+        assert!(!population.is_empty());
+        assert!(!stop.is_empty(), "run would never stop");
+
+        // `Instant` isn't available on all platforms (e.g. wasm32), so we
+        // only touch it when actually asked for
+        let started_at = stop.time_budget.map(|_| Instant::now());
+
+        let mut evaluate = |chromosome: Chromosome<G>| Evaluated {
+            fitness: evaluate(&chromosome),
+            chromosome,
+        };
+
+        let mut population: Vec<_> = population.into_iter().map(&mut evaluate).collect();
+        let mut best: Option<Evaluated<G>> = None;
+        let mut stagnant_for = 0;
+        let mut generation = 0;
+        let mut bound_hits = 0;
+
+        loop {
+            let statistics = Statistics {
+                bound_hits,
+                ..Statistics::new(&population)
+            };
+            observer.observe(generation, &statistics);
+
+            let fittest = population
+                .iter()
+                .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+                .expect("population should not be empty");
+
+            match &best {
+                Some(best) if best.fitness >= fittest.fitness => stagnant_for += 1,
+                _ => {
+                    best = Some(fittest.clone());
+                    stagnant_for = 0;
+                }
+            }
+
+            let reason = if stop
+                .target_fitness
+                .is_some_and(|target| statistics.max_fitness >= target)
+            {
+                Some(StopReason::TargetFitness)
+            } else if stop.max_generations.is_some_and(|max| generation >= max) {
+                Some(StopReason::MaxGenerations)
+            } else if stop.stagnation.is_some_and(|max| stagnant_for >= max) {
+                Some(StopReason::Stagnation)
+            } else if started_at
+                .zip(stop.time_budget)
+                .is_some_and(|(started_at, budget)| started_at.elapsed() >= budget)
+            {
+                Some(StopReason::TimeBudget)
+            } else {
+                None
+            };
+
+            if let Some(reason) = reason {
+                return RunOutcome {
+                    population,
+                    best: best.expect("best should have been set"),
+                    generations: generation,
+                    reason,
+                    statistics,
+                };
+            }
+
+            let (children, evolved) = self.evolve(rng, &population);
+            bound_hits = evolved.bound_hits;
+
+            population = children
+                .into_iter()
+                .map(|child: Evaluated<G>| evaluate(child.chromosome))
+                .collect();
+
+            generation += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::Arc;
+
+    fn ga() -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        )
+    }
+
+    fn population(rng: &mut dyn RngCore) -> Vec<Chromosome> {
+        (0..20)
+            .map(|_| (0..5).map(|_| rng.gen_range(0.0..1.0)).collect())
+            .collect()
+    }
+
+    // The closer all genes get to 3.0, the better
+    fn evaluate(chromosome: &Chromosome) -> f32 {
+        let error: f32 = chromosome.iter().map(|gene| (gene - 3.0).abs()).sum();
+        100.0 / (1.0 + error)
+    }
+
+    #[test]
+    fn reaches_target_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+        let mut observed = Vec::new();
+
+        let outcome = ga().run(
+            &mut rng,
+            population,
+            evaluate,
+            &StopConditions::new()
+                .target_fitness(25.0)
+                .max_generations(1000),
+            &mut |generation, statistics: &Statistics| {
+                observed.push((generation, statistics.max_fitness))
+            },
+        );
+
+        assert_eq!(outcome.reason, StopReason::TargetFitness);
+        assert!(outcome.best.fitness >= 25.0);
+        assert!(outcome.generations < 1000);
+        assert_eq!(observed.len(), outcome.generations + 1);
+        assert_eq!(observed[0].0, 0);
+    }
+
+    #[test]
+    fn stops_after_max_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+        let mut calls = 0;
+
+        let outcome = ga().run(
+            &mut rng,
+            population,
+            evaluate,
+            &StopConditions::new().max_generations(7),
+            &mut |_, _: &Statistics| calls += 1,
+        );
+
+        assert_eq!(outcome.reason, StopReason::MaxGenerations);
+        assert_eq!(outcome.generations, 7);
+        assert_eq!(calls, 8);
+    }
+
+    #[test]
+    fn stops_on_stagnation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let outcome = ga().run(
+            &mut rng,
+            population,
+            |_| 1.0,
+            &StopConditions::new().stagnation(5).max_generations(100),
+            &mut |_, _: &Statistics| {},
+        );
+
+        assert_eq!(outcome.reason, StopReason::Stagnation);
+        assert_eq!(outcome.generations, 5);
+    }
+
+    #[test]
+    fn reports_bound_hits_of_offspring() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let bounds = Arc::new(Bounds::global(0.0, 1.0, BoundPolicy::Clamp));
+
+        let population = population(&mut rng)
+            .into_iter()
+            .map(|chromosome| chromosome.with_bounds(bounds.clone()))
+            .collect();
+
+        let mut bound_hits = Vec::new();
+
+        ga().run(
+            &mut rng,
+            population,
+            evaluate,
+            &StopConditions::new().max_generations(3),
+            &mut |_, statistics: &Statistics| bound_hits.push(statistics.bound_hits),
+        );
+
+        // The initial population hasn't been bred, but genes pushed towards
+        // 3.0 keep hitting the bounds afterwards
+        assert_eq!(bound_hits[0], 0);
+        assert!(bound_hits[1..].iter().all(|&hits| hits > 0));
+    }
+
+    #[test]
+    fn injects_immigrants() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let ga = ga().with_immigrants(Immigrants::new(
+            0.25,
+            ImmigrantSource::Random {
+                min: 10.0,
+                max: 11.0,
+            },
+        ));
+
+        let outcome = ga.run(
+            &mut rng,
+            population,
+            evaluate,
+            &StopConditions::new().max_generations(1),
+            &mut |_, _: &Statistics| {},
+        );
+
+        // Natives' genes stay close to their initial `0.0..1.0`
+        let immigrants = outcome
+            .population
+            .iter()
+            .filter(|individual| individual.chromosome.iter().all(|gene| *gene >= 10.0))
+            .count();
+
+        assert_eq!(immigrants, 5);
+    }
+
+    #[test]
+    fn stops_when_out_of_time() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let outcome = ga().run(
+            &mut rng,
+            population,
+            evaluate,
+            &StopConditions::new().time_budget(Duration::ZERO),
+            &mut |_, _: &Statistics| {},
+        );
+
+        assert_eq!(outcome.reason, StopReason::TimeBudget);
+        assert_eq!(outcome.generations, 0);
+    }
+}
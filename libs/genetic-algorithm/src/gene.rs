@@ -0,0 +1,228 @@
+use crate::Bounds;
+use rand::{Rng, RngCore};
+use std::fmt::Debug;
+
+pub trait Gene: Clone + Debug {
+    // How far apart two alleles are - used to compare genotypes (e.g. by
+    // `HallOfFame`) and to measure what a mutation did
+    fn distance(&self, other: &Self) -> f32;
+
+    // Returns a random allele of the same kind as this one; numeric genes
+    // are drawn from `min..=max`, other ones ignore the range
+    fn random_in(&self, rng: &mut dyn RngCore, min: f32, max: f32) -> Self;
+
+    // Brings the gene back within `min..=max`, as dictated by `bounds`'
+    // policy, and returns whether it was out of range; genes that aren't
+    // numbers have no range to be out of
+    fn constrain(
+        &mut self,
+        _rng: &mut dyn RngCore,
+        _bounds: &Bounds,
+        _min: f32,
+        _max: f32,
+    ) -> bool {
+        false
+    }
+}
+
+impl Gene for f32 {
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs()
+    }
+
+    fn random_in(&self, rng: &mut dyn RngCore, min: f32, max: f32) -> Self {
+        rng.gen_range(min..=max)
+    }
+
+    fn constrain(&mut self, rng: &mut dyn RngCore, bounds: &Bounds, min: f32, max: f32) -> bool {
+        match bounds.constrain(rng, *self, min, max) {
+            Some(value) => {
+                *self = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Gene for f64 {
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs() as f32
+    }
+
+    fn random_in(&self, rng: &mut dyn RngCore, min: f32, max: f32) -> Self {
+        rng.gen_range(min as f64..=max as f64)
+    }
+
+    fn constrain(&mut self, rng: &mut dyn RngCore, bounds: &Bounds, min: f32, max: f32) -> bool {
+        if (min as f64..=max as f64).contains(self) {
+            return false;
+        }
+
+        let mut value = *self as f32;
+        value.constrain(rng, bounds, min, max);
+        *self = value as f64;
+        true
+    }
+}
+
+impl Gene for bool {
+    fn distance(&self, other: &Self) -> f32 {
+        if self == other { 0.0 } else { 1.0 }
+    }
+
+    fn random_in(&self, rng: &mut dyn RngCore, _min: f32, _max: f32) -> Self {
+        rng.gen_bool(0.5)
+    }
+}
+
+// Integer allele that always stays within `min..=max`, e.g. a photoreceptor
+// count
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundedInteger {
+    value: i32,
+    min: i32,
+    max: i32,
+}
+
+impl BoundedInteger {
+    pub fn new(value: i32, min: i32, max: i32) -> Self {
+        assert!(min <= max);
+
+        Self {
+            value: value.clamp(min, max),
+            min,
+            max,
+        }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn set(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+    }
+}
+
+impl Gene for BoundedInteger {
+    fn distance(&self, other: &Self) -> f32 {
+        (self.value - other.value).abs() as f32
+    }
+
+    // Always stays within the gene's own bounds
+    fn random_in(&self, rng: &mut dyn RngCore, min: f32, max: f32) -> Self {
+        let min = (min.ceil() as i32).max(self.min);
+        let max = (max.floor() as i32).min(self.max);
+
+        let value = if min <= max {
+            rng.gen_range(min..=max)
+        } else {
+            rng.gen_range(self.min..=self.max)
+        };
+
+        Self { value, ..*self }
+    }
+}
+
+// Gene of a record-like genome, where each locus has its own type - e.g.
+// a couple of weights followed by a sensor count and a sensor toggle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MixedGene {
+    Float(f32),
+    Integer(BoundedInteger),
+    Bool(bool),
+}
+
+impl Gene for MixedGene {
+    fn distance(&self, other: &Self) -> f32 {
+        match (self, other) {
+            (Self::Float(a), Self::Float(b)) => a.distance(b),
+            (Self::Integer(a), Self::Integer(b)) => a.distance(b),
+            (Self::Bool(a), Self::Bool(b)) => a.distance(b),
+            _ => f32::INFINITY,
+        }
+    }
+
+    fn random_in(&self, rng: &mut dyn RngCore, min: f32, max: f32) -> Self {
+        match self {
+            Self::Float(gene) => Self::Float(gene.random_in(rng, min, max)),
+            Self::Integer(gene) => Self::Integer(gene.random_in(rng, min, max)),
+            Self::Bool(gene) => Self::Bool(gene.random_in(rng, min, max)),
+        }
+    }
+
+    fn constrain(&mut self, rng: &mut dyn RngCore, bounds: &Bounds, min: f32, max: f32) -> bool {
+        match self {
+            Self::Float(gene) => gene.constrain(rng, bounds, min, max),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn bounded_integer_stays_within_bounds() {
+        let mut gene = BoundedInteger::new(15, 3, 11);
+        assert_eq!(gene.value(), 11);
+
+        gene.set(-4);
+        assert_eq!(gene.value(), 3);
+
+        gene.set(7);
+        assert_eq!(gene.value(), 7);
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(1.5f32.distance(&-0.5), 2.0);
+        assert_eq!(1.5f64.distance(&-0.5), 2.0);
+        assert_eq!(true.distance(&false), 1.0);
+        assert_eq!(true.distance(&true), 0.0);
+
+        assert_eq!(
+            BoundedInteger::new(3, 0, 9).distance(&BoundedInteger::new(7, 0, 9)),
+            4.0
+        );
+
+        assert_eq!(
+            MixedGene::Float(1.0).distance(&MixedGene::Float(0.25)),
+            0.75
+        );
+
+        assert_eq!(
+            MixedGene::Float(1.0).distance(&MixedGene::Bool(true)),
+            f32::INFINITY
+        );
+    }
+
+    #[test]
+    fn random_alleles() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for _ in 0..100 {
+            let gene = 0.0f32.random_in(&mut rng, -0.5, 0.5);
+            assert!((-0.5..=0.5).contains(&gene));
+
+            let gene = BoundedInteger::new(5, 3, 11).random_in(&mut rng, -100.0, 4.0);
+            assert!((3..=4).contains(&gene.value()));
+            assert_eq!((gene.min(), gene.max()), (3, 11));
+
+            let gene = MixedGene::Float(7.0).random_in(&mut rng, 0.0, 1.0);
+            assert!(matches!(gene, MixedGene::Float(value) if (0.0..=1.0).contains(&value)));
+        }
+    }
+}
@@ -0,0 +1,507 @@
+use crate::*;
+use std::io::{self, Write};
+
+pub type IndividualId = u64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lineage {
+    pub id: IndividualId,
+    pub parents: Vec<IndividualId>,
+    pub generation: usize,
+    pub mutation: MutationRecord,
+}
+
+// What the mutation method did to a freshly crossed-over child; it's
+// computed by diffing the chromosome before and after mutating it, so
+// it works for any `MutationMethod`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MutationRecord {
+    pub genes_mutated: usize,
+    pub magnitude: f32,
+}
+
+impl MutationRecord {
+    pub fn between<G>(before: &Chromosome<G>, after: &Chromosome<G>) -> Self
+    where
+        G: Gene,
+    {
+        assert_eq!(before.len(), after.len());
+
+        let mut record = Self::default();
+
+        for (a, b) in before.iter().zip(after.iter()) {
+            let distance = a.distance(b);
+
+            if distance != 0.0 {
+                record.genes_mutated += 1;
+                record.magnitude += distance;
+            }
+        }
+
+        record
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Genealogy {
+    generation: usize,
+    records: Vec<Lineage>,
+    fitness: Vec<Option<f32>>,
+    // Individuals replaced before they got evaluated, see `replace()`
+    discarded: Vec<bool>,
+}
+
+impl Genealogy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn records(&self) -> &[Lineage] {
+        &self.records
+    }
+
+    pub fn get(&self, id: IndividualId) -> Option<&Lineage> {
+        self.records.get(id as usize)
+    }
+
+    pub fn fitness(&self, id: IndividualId) -> Option<f32> {
+        self.fitness.get(id as usize).copied().flatten()
+    }
+
+    // Registers an individual that wasn't bred by the algorithm (e.g. one
+    // of the randomly generated individuals of the very first generation)
+    pub fn founder(&mut self) -> Lineage {
+        self.push(Vec::new(), MutationRecord::default())
+    }
+
+    // Whether given individual got replaced (e.g. by a hall-of-fame genome or
+    // an immigrant) before it was evaluated; such individuals are left out
+    // of the exports
+    pub fn is_discarded(&self, id: IndividualId) -> bool {
+        self.discarded.get(id as usize).copied().unwrap_or(false)
+    }
+
+    // Registers `newcomer` (which didn't come from breeding) as a founder,
+    // discarding `replaced`, whose place it takes
+    pub(crate) fn replace<I, G>(&mut self, replaced: &I, newcomer: &mut I)
+    where
+        I: Individual<G>,
+    {
+        if let Some(lineage) = replaced.lineage()
+            && let Some(discarded) = self.discarded.get_mut(lineage.id as usize)
+        {
+            *discarded = true;
+        }
+
+        newcomer.set_lineage(self.founder());
+    }
+
+    pub fn record_fitness(&mut self, id: IndividualId, fitness: f32) {
+        if let Some(slot) = self.fitness.get_mut(id as usize) {
+            *slot = Some(fitness);
+        }
+    }
+
+    // Returns all ancestors of given individual, closest ones first; parents
+    // recorded in another genealogy are skipped
+    pub fn ancestors(&self, id: IndividualId) -> Vec<IndividualId> {
+        let mut ancestors = Vec::new();
+        let mut visited = vec![false; self.records.len()];
+        let mut queue = std::collections::VecDeque::from([id]);
+
+        while let Some(id) = queue.pop_front() {
+            let Some(lineage) = self.get(id) else {
+                continue;
+            };
+
+            for &parent in &lineage.parents {
+                if let Some(visited) = visited.get_mut(parent as usize)
+                    && !std::mem::replace(visited, true)
+                {
+                    ancestors.push(parent);
+                    queue.push_back(parent);
+                }
+            }
+        }
+
+        ancestors
+    }
+
+    pub fn write_dot(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "digraph pedigree {{")?;
+        writeln!(out, "    rankdir=TB;")?;
+
+        for lineage in self.kept() {
+            let fitness = match self.fitness(lineage.id) {
+                Some(fitness) => format!("{fitness:.2}"),
+                None => "?".to_string(),
+            };
+
+            writeln!(
+                out,
+                "    n{} [label=\"#{} gen={} fitness={} mutated={}\"];",
+                lineage.id, lineage.id, lineage.generation, fitness, lineage.mutation.genes_mutated,
+            )?;
+        }
+
+        for lineage in self.kept() {
+            for parent in &lineage.parents {
+                writeln!(out, "    n{} -> n{};", parent, lineage.id)?;
+            }
+        }
+
+        writeln!(out, "}}")
+    }
+
+    pub fn write_nodes_csv(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "id,generation,fitness,genes_mutated,mutation_magnitude"
+        )?;
+
+        for lineage in self.kept() {
+            let fitness = self
+                .fitness(lineage.id)
+                .map(|fitness| format!("{fitness:.6}"))
+                .unwrap_or_default();
+
+            writeln!(
+                out,
+                "{},{},{},{},{:.6}",
+                lineage.id,
+                lineage.generation,
+                fitness,
+                lineage.mutation.genes_mutated,
+                lineage.mutation.magnitude,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_edges_csv(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "parent,child,child_generation")?;
+
+        for lineage in self.kept() {
+            for parent in &lineage.parents {
+                writeln!(out, "{},{},{}", parent, lineage.id, lineage.generation)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn next_generation(&mut self) {
+        self.generation += 1;
+    }
+
+    pub(crate) fn child(
+        &mut self,
+        parents: Vec<IndividualId>,
+        mutation: MutationRecord,
+    ) -> Lineage {
+        self.push(parents, mutation)
+    }
+
+    fn push(&mut self, parents: Vec<IndividualId>, mutation: MutationRecord) -> Lineage {
+        let lineage = Lineage {
+            id: self.records.len() as IndividualId,
+            parents,
+            generation: self.generation,
+            mutation,
+        };
+
+        self.records.push(lineage.clone());
+        self.fitness.push(None);
+        self.discarded.push(false);
+        lineage
+    }
+
+    // Records that haven't been discarded
+    fn kept(&self) -> impl Iterator<Item = &Lineage> {
+        self.records
+            .iter()
+            .filter(|lineage| !self.is_discarded(lineage.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    struct TrackedIndividual {
+        chromosome: Chromosome,
+        lineage: Option<Lineage>,
+    }
+
+    impl Individual for TrackedIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self {
+                chromosome,
+                lineage: None,
+            }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+
+        fn lineage(&self) -> Option<&Lineage> {
+            self.lineage.as_ref()
+        }
+
+        fn set_lineage(&mut self, lineage: Lineage) {
+            self.lineage = Some(lineage);
+        }
+    }
+
+    fn founders(genealogy: &mut Genealogy) -> Vec<TrackedIndividual> {
+        [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [1.0, 2.0, 1.0],
+            [1.0, 2.0, 4.0],
+        ]
+        .into_iter()
+        .map(|genes| {
+            let mut individual = TrackedIndividual::create(genes.into_iter().collect());
+            individual.set_lineage(genealogy.founder());
+            individual
+        })
+        .collect()
+    }
+
+    #[test]
+    fn mutation_record() {
+        let before: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+        let after: Chromosome = vec![1.5, 2.0, 2.0].into_iter().collect();
+        let record = MutationRecord::between(&before, &after);
+
+        assert_eq!(record.genes_mutated, 2);
+        approx::assert_relative_eq!(record.magnitude, 1.5);
+    }
+
+    #[test]
+    fn tracks_parents_and_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut genealogy = Genealogy::new();
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let mut population = founders(&mut genealogy);
+
+        for _ in 0..3 {
+            (population, _) = ga.evolve_with_genealogy(&mut rng, &population, &mut genealogy);
+        }
+
+        assert_eq!(genealogy.len(), 16);
+        assert_eq!(genealogy.generation(), 3);
+
+        for individual in &population {
+            let lineage = individual.lineage().unwrap();
+
+            assert_eq!(lineage.generation, 3);
+            assert!(!lineage.parents.is_empty() && lineage.parents.len() <= 2);
+
+            for &parent in &lineage.parents {
+                assert_eq!(genealogy.get(parent).unwrap().generation, 2);
+                assert!(genealogy.fitness(parent).is_some());
+            }
+
+            let ancestors = genealogy.ancestors(lineage.id);
+            assert!(ancestors.iter().any(|&id| id < 4));
+        }
+
+        // The last generation hasn't been evaluated yet
+        assert_eq!(genealogy.fitness(15), None);
+    }
+
+    #[test]
+    fn ancestors_from_another_genealogy() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut other = Genealogy::new();
+
+        for _ in 0..16 {
+            other.founder();
+        }
+
+        let population = founders(&mut other);
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let mut genealogy = Genealogy::new();
+        let (children, _) = ga.evolve_with_genealogy(&mut rng, &population, &mut genealogy);
+
+        for child in &children {
+            let lineage = child.lineage().unwrap();
+
+            assert!(lineage.parents.iter().all(|&parent| parent >= 16));
+            assert_eq!(genealogy.ancestors(lineage.id), vec![]);
+        }
+    }
+
+    #[test]
+    fn does_not_change_evolution() {
+        let mut genealogy = Genealogy::new();
+        let tracked = founders(&mut genealogy);
+        let untracked: Vec<_> = tracked
+            .iter()
+            .map(|individual| TrackedIndividual::create(individual.chromosome.clone()))
+            .collect();
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (tracked, _) = ga.evolve_with_genealogy(&mut rng, &tracked, &mut genealogy);
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (untracked, _) = ga.evolve(&mut rng, &untracked);
+
+        for (a, b) in tracked.iter().zip(&untracked) {
+            assert_eq!(a.chromosome, b.chromosome);
+        }
+    }
+
+    #[test]
+    fn newcomers_replace_children() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut genealogy = Genealogy::new();
+        let mut hall_of_fame = HallOfFame::new(4);
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_immigrants(Immigrants::new(
+            0.25,
+            ImmigrantSource::Random {
+                min: -1.0,
+                max: 1.0,
+            },
+        ));
+
+        let population = founders(&mut genealogy);
+        hall_of_fame.update(&population, 0);
+
+        // Children get ids 4..=7, and an immigrant (id 8) replaces one of them
+        let (mut children, _) = ga.evolve_with_genealogy(&mut rng, &population, &mut genealogy);
+
+        hall_of_fame.reinject(&mut rng, &mut children, 1, Some(&mut genealogy));
+
+        assert_eq!(genealogy.len(), 10);
+
+        let ids: Vec<_> = children
+            .iter()
+            .map(|child| child.lineage().unwrap().id)
+            .collect();
+
+        // Newcomers don't pretend to be their archived ancestors
+        assert!(ids.contains(&9));
+        assert!(ids.iter().all(|&id| id >= 4));
+
+        for &id in ids.iter().filter(|&&id| id >= 8) {
+            let lineage = genealogy.get(id).unwrap();
+
+            assert!(lineage.parents.is_empty());
+            assert_eq!(lineage.generation, 1);
+        }
+
+        // Whoever they replaced is gone
+        let discarded: Vec<_> = (4..10).filter(|id| !ids.contains(id)).collect();
+
+        assert!(!discarded.is_empty());
+
+        for id in 0..10 {
+            assert_eq!(genealogy.is_discarded(id), discarded.contains(&id));
+        }
+
+        let mut nodes = Vec::new();
+        genealogy.write_nodes_csv(&mut nodes).unwrap();
+
+        assert_eq!(
+            String::from_utf8(nodes).unwrap().lines().count(),
+            1 + 10 - discarded.len()
+        );
+    }
+
+    #[test]
+    fn exports() {
+        let mut genealogy = Genealogy::new();
+        let a = genealogy.founder();
+        let b = genealogy.founder();
+
+        genealogy.next_generation();
+        genealogy.record_fitness(a.id, 2.0);
+
+        let child = genealogy.child(
+            vec![a.id, b.id],
+            MutationRecord {
+                genes_mutated: 1,
+                magnitude: 0.25,
+            },
+        );
+
+        assert_eq!(child.id, 2);
+        assert_eq!(child.generation, 1);
+
+        let mut dot = Vec::new();
+        genealogy.write_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+
+        assert!(dot.starts_with("digraph pedigree {"));
+        assert!(dot.contains("n0 [label=\"#0 gen=0 fitness=2.00 mutated=0\"];"));
+        assert!(dot.contains("n1 [label=\"#1 gen=0 fitness=? mutated=0\"];"));
+        assert!(dot.contains("n0 -> n2;"));
+        assert!(dot.contains("n1 -> n2;"));
+
+        let mut edges = Vec::new();
+        genealogy.write_edges_csv(&mut edges).unwrap();
+
+        assert_eq!(
+            String::from_utf8(edges).unwrap(),
+            "parent,child,child_generation\n0,2,1\n1,2,1\n"
+        );
+
+        let mut nodes = Vec::new();
+        genealogy.write_nodes_csv(&mut nodes).unwrap();
+
+        assert_eq!(
+            String::from_utf8(nodes).unwrap(),
+            "id,generation,fitness,genes_mutated,mutation_magnitude\n\
+             0,0,2.000000,0,0.000000\n\
+             1,0,,0,0.000000\n\
+             2,1,,1,0.250000\n"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use crate::*;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 8] = b"SLGENOME";
+const VERSION: u16 = 1;
+
+// A chromosome together with everything needed to make sense of it in
+// another run: which species it came from and the topology of the brain
+// its genes encode.
+//
+// Binary layout (all integers little-endian):
+//
+//   magic        8 bytes, "SLGENOME"
+//   version      u16
+//   species      u16 length + UTF-8 bytes
+//   topology     u16 layer count + u32 per layer
+//   gene count   u32
+//   genes        f32 per gene
+//   checksum     u32, CRC-32 of everything above
+#[derive(Clone, Debug)]
+pub struct Genome {
+    pub species: String,
+    pub topology: Vec<usize>,
+    pub chromosome: Chromosome,
+}
+
+#[derive(Debug)]
+pub enum GenomeError {
+    Io(io::Error),
+    Truncated,
+    BadMagic,
+    UnsupportedVersion(u16),
+    ChecksumMismatch { expected: u32, actual: u32 },
+    InvalidLength(usize),
+    InvalidSpecies,
+    TrailingBytes(usize),
+    Incompatible(String),
+    TooLong { field: &'static str, len: usize },
+}
+
+impl fmt::Display for GenomeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "i/o error: {err}"),
+            Self::Truncated => write!(f, "genome data is truncated"),
+            Self::BadMagic => write!(f, "not a genome file (bad magic header)"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported genome format version {version}")
+            }
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch (expected {expected:#010x}, got {actual:#010x})"
+            ),
+            Self::InvalidLength(len) => {
+                write!(f, "{len} bytes is not a whole number of genes")
+            }
+            Self::InvalidSpecies => write!(f, "species name is not valid UTF-8"),
+            Self::TrailingBytes(len) => write!(f, "{len} unexpected bytes after the genome"),
+            Self::Incompatible(reason) => write!(f, "incompatible genome: {reason}"),
+            Self::TooLong { field, len } => {
+                write!(f, "{field} is too long to encode ({len})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenomeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GenomeError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl Genome {
+    pub fn new(species: impl Into<String>, topology: Vec<usize>, chromosome: Chromosome) -> Self {
+        Self {
+            species: species.into(),
+            topology,
+            chromosome,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, GenomeError> {
+        let genes = self.chromosome.to_bytes();
+        let mut out = Vec::with_capacity(32 + self.species.len() + genes.len());
+
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        out.extend_from_slice(
+            &encode_len::<u16>("species name", self.species.len())?.to_le_bytes(),
+        );
+        out.extend_from_slice(self.species.as_bytes());
+        out.extend_from_slice(&encode_len::<u16>("topology", self.topology.len())?.to_le_bytes());
+
+        for &neurons in &self.topology {
+            out.extend_from_slice(&encode_len::<u32>("layer", neurons)?.to_le_bytes());
+        }
+
+        out.extend_from_slice(
+            &encode_len::<u32>("chromosome", self.chromosome.len())?.to_le_bytes(),
+        );
+        out.extend_from_slice(&genes);
+
+        let checksum = crc32(&out);
+        out.extend_from_slice(&checksum.to_le_bytes());
+        Ok(out)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GenomeError> {
+        let mut reader = ByteReader { bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(GenomeError::BadMagic);
+        }
+
+        let version = reader.u16()?;
+        if version != VERSION {
+            return Err(GenomeError::UnsupportedVersion(version));
+        }
+
+        let species_len = reader.u16()? as usize;
+        let species = std::str::from_utf8(reader.take(species_len)?)
+            .map_err(|_| GenomeError::InvalidSpecies)?
+            .to_string();
+
+        let layers = reader.u16()? as usize;
+        let topology = (0..layers)
+            .map(|_| reader.u32().map(|neurons| neurons as usize))
+            .collect::<Result<_, _>>()?;
+
+        let genes = reader.u32()? as usize;
+        let genes_len = genes.checked_mul(4).ok_or(GenomeError::Truncated)?;
+        let chromosome = Chromosome::from_bytes(reader.take(genes_len)?)?;
+
+        let checked = bytes.len() - reader.bytes.len();
+        let expected = reader.u32()?;
+        let actual = crc32(&bytes[..checked]);
+
+        if expected != actual {
+            return Err(GenomeError::ChecksumMismatch { expected, actual });
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(GenomeError::TrailingBytes(reader.bytes.len()));
+        }
+
+        Ok(Self {
+            species,
+            topology,
+            chromosome,
+        })
+    }
+
+    pub fn write(&self, out: &mut dyn Write) -> Result<(), GenomeError> {
+        out.write_all(&self.to_bytes()?)?;
+        Ok(())
+    }
+
+    pub fn read(input: &mut dyn Read) -> Result<Self, GenomeError> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], GenomeError> {
+        if self.bytes.len() < len {
+            return Err(GenomeError::Truncated);
+        }
+
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> Result<u16, GenomeError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, GenomeError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+// Converts a length into the integer type it's stored as
+fn encode_len<T: TryFrom<usize>>(field: &'static str, len: usize) -> Result<T, GenomeError> {
+    T::try_from(len).map_err(|_| GenomeError::TooLong { field, len })
+}
+
+// CRC-32 (IEEE 802.3), bit by bit - genomes are small enough for the
+// lookup table not to matter
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome() -> Genome {
+        Genome::new(
+            "prey",
+            vec![18, 9, 2],
+            vec![0.5, -1.25, 3.0].into_iter().collect(),
+        )
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn chromosome_round_trip() {
+        let chromosome: Chromosome = vec![0.1, -0.2, f32::MAX, 0.0].into_iter().collect();
+        let actual = Chromosome::from_bytes(&chromosome.to_bytes()).unwrap();
+
+        assert_eq!(actual, chromosome);
+    }
+
+    #[test]
+    fn chromosome_invalid_length() {
+        assert!(matches!(
+            Chromosome::from_bytes(&[0, 0, 0, 0, 0]),
+            Err(GenomeError::InvalidLength(5))
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bytes = Vec::new();
+        genome().write(&mut bytes).unwrap();
+
+        let actual = Genome::read(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(actual.species, "prey");
+        assert_eq!(actual.topology, vec![18, 9, 2]);
+        assert_eq!(actual.chromosome, genome().chromosome);
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = genome().to_bytes().unwrap();
+
+        assert!(matches!(
+            Genome::from_bytes(b"NOTAGENOMEFILE"),
+            Err(GenomeError::BadMagic)
+        ));
+
+        assert!(matches!(
+            Genome::from_bytes(&bytes[..bytes.len() - 6]),
+            Err(GenomeError::Truncated)
+        ));
+
+        let mut corrupted = bytes.clone();
+        corrupted[36] ^= 0x40;
+        assert!(matches!(
+            Genome::from_bytes(&corrupted),
+            Err(GenomeError::ChecksumMismatch { .. })
+        ));
+
+        let mut newer = bytes.clone();
+        newer[8] = 2;
+        assert!(matches!(
+            Genome::from_bytes(&newer),
+            Err(GenomeError::UnsupportedVersion(2))
+        ));
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(matches!(
+            Genome::from_bytes(&trailing),
+            Err(GenomeError::TrailingBytes(1))
+        ));
+    }
+
+    #[test]
+    fn too_long() {
+        let mut long_species = genome();
+        long_species.species = "x".repeat(u16::MAX as usize + 1);
+
+        assert!(matches!(
+            long_species.to_bytes(),
+            Err(GenomeError::TooLong {
+                field: "species name",
+                len: 65536
+            })
+        ));
+
+        let mut deep_topology = genome();
+        deep_topology.topology = vec![1; u16::MAX as usize + 1];
+
+        assert!(matches!(
+            deep_topology.write(&mut Vec::new()),
+            Err(GenomeError::TooLong {
+                field: "topology",
+                len: 65536
+            })
+        ));
+    }
+}
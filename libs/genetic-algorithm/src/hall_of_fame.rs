@@ -0,0 +1,349 @@
+use crate::*;
+use std::io::{self, BufRead, Write};
+
+const HEADER: &str = "# hall-of-fame v1";
+
+#[derive(Clone, Debug)]
+pub struct HallOfFameEntry<G = f32> {
+    pub chromosome: Chromosome<G>,
+    pub fitness: f32,
+    pub generation: usize,
+    pub lineage: Option<Lineage>,
+}
+
+// Bounded archive of the fittest chromosomes seen so far, kept sorted from
+// the best to the worst one.
+//
+// Two chromosomes closer to each other than `min_distance` are considered
+// the same genotype, and only the fitter one is kept - with the default
+// distance of zero only exact duplicates are merged.
+#[derive(Clone, Debug)]
+pub struct HallOfFame<G = f32> {
+    capacity: usize,
+    min_distance: f32,
+    entries: Vec<HallOfFameEntry<G>>,
+}
+
+impl<G> HallOfFame<G>
+where
+    G: Gene,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            min_distance: 0.0,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn with_min_distance(mut self, min_distance: f32) -> Self {
+        assert!(min_distance >= 0.0);
+
+        self.min_distance = min_distance;
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[HallOfFameEntry<G>] {
+        &self.entries
+    }
+
+    pub fn best(&self) -> Option<&HallOfFameEntry<G>> {
+        self.entries.first()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn update<I>(&mut self, population: &[I], generation: usize)
+    where
+        I: Individual<G>,
+    {
+        for individual in population {
+            self.insert(HallOfFameEntry {
+                chromosome: individual.chromosome().clone(),
+                fitness: individual.fitness(),
+                generation,
+                lineage: individual.lineage().cloned(),
+            });
+        }
+    }
+
+    // Returns whether the entry made it into the archive
+    pub fn insert(&mut self, entry: HallOfFameEntry<G>) -> bool {
+        if self.capacity == 0 || !entry.fitness.is_finite() {
+            return false;
+        }
+
+        let duplicate = self.entries.iter().position(|other| {
+            other.chromosome.len() == entry.chromosome.len()
+                && other.chromosome.distance(&entry.chromosome) <= self.min_distance
+        });
+
+        if let Some(duplicate) = duplicate {
+            if self.entries[duplicate].fitness >= entry.fitness {
+                return false;
+            }
+
+            self.entries.remove(duplicate);
+        } else if self.entries.len() == self.capacity {
+            if self.entries[self.capacity - 1].fitness >= entry.fitness {
+                return false;
+            }
+
+            self.entries.pop();
+        }
+
+        let idx = self
+            .entries
+            .partition_point(|other| other.fitness >= entry.fitness);
+
+        self.entries.insert(idx, entry);
+        true
+    }
+
+    // Replaces `count` randomly chosen members of `population` with copies
+    // of the best archived chromosomes; it's meant to be called on a freshly
+    // evolved generation, before its individuals get evaluated.
+    //
+    // Entries whose chromosome doesn't match the population's (e.g. loaded
+    // from a run with a different brain topology) are skipped.
+    //
+    // Given `genealogy`, the copies are registered as founders, since the
+    // archived lineage might come from long ago (or another run).
+    pub fn reinject<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &mut [I],
+        count: usize,
+        mut genealogy: Option<&mut Genealogy>,
+    ) where
+        I: Individual<G>,
+    {
+        if count == 0 {
+            return;
+        }
+
+        let Some(genes) = population
+            .first()
+            .map(|individual| individual.chromosome().len())
+        else {
+            return;
+        };
+
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.chromosome.len() == genes)
+            .take(count)
+            .collect();
+
+        let slots =
+            rand::seq::index::sample(rng, population.len(), entries.len().min(population.len()));
+
+        for (slot, entry) in slots.into_iter().zip(entries) {
+            let mut individual = I::create(entry.chromosome.clone());
+
+            if let Some(genealogy) = genealogy.as_deref_mut() {
+                genealogy.replace(&population[slot], &mut individual);
+            }
+
+            population[slot] = individual;
+        }
+    }
+}
+
+impl HallOfFame {
+    // Text format: a header line, then one entry per line as
+    // `fitness generation gene gene gene ...`
+    pub fn save(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{HEADER}")?;
+
+        for entry in &self.entries {
+            write!(out, "{} {}", entry.fitness, entry.generation)?;
+
+            for gene in entry.chromosome.iter() {
+                write!(out, " {gene}")?;
+            }
+
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn load(input: &mut dyn BufRead, capacity: usize) -> io::Result<Self> {
+        let mut lines = input.lines();
+
+        if lines.next().transpose()?.as_deref().map(str::trim) != Some(HEADER) {
+            return Err(invalid_data("missing hall-of-fame header"));
+        }
+
+        let mut hall_of_fame = Self::new(capacity);
+
+        for (idx, line) in lines.enumerate() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+
+            let Some(fitness) = fields.next() else {
+                continue;
+            };
+
+            let parse_error = || invalid_data(&format!("malformed entry at line {}", idx + 2));
+
+            let fitness = fitness.parse().map_err(|_| parse_error())?;
+
+            let generation = fields
+                .next()
+                .and_then(|generation| generation.parse().ok())
+                .ok_or_else(parse_error)?;
+
+            let chromosome = fields
+                .map(|gene| gene.parse::<f32>())
+                .collect::<Result<Chromosome, _>>()
+                .map_err(|_| parse_error())?;
+
+            hall_of_fame.insert(HallOfFameEntry {
+                chromosome,
+                fitness,
+                generation,
+                lineage: None,
+            });
+        }
+
+        Ok(hall_of_fame)
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn entry(genes: &[f32], fitness: f32) -> HallOfFameEntry {
+        HallOfFameEntry {
+            chromosome: genes.iter().copied().collect(),
+            fitness,
+            generation: 0,
+            lineage: None,
+        }
+    }
+
+    fn fitnesses(hall_of_fame: &HallOfFame) -> Vec<f32> {
+        hall_of_fame
+            .entries()
+            .iter()
+            .map(|entry| entry.fitness)
+            .collect()
+    }
+
+    #[test]
+    fn keeps_top_n() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        for (idx, fitness) in [2.0, 5.0, 1.0, 4.0, 3.0].into_iter().enumerate() {
+            hall_of_fame.insert(entry(&[idx as f32], fitness));
+        }
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![5.0, 4.0, 3.0]);
+        assert!(!hall_of_fame.insert(entry(&[9.0], 2.5)));
+    }
+
+    #[test]
+    fn merges_duplicates() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        hall_of_fame.insert(entry(&[1.0, 1.0], 2.0));
+        hall_of_fame.insert(entry(&[1.0, 1.0], 2.0));
+        hall_of_fame.insert(entry(&[1.0, 1.0], 3.0));
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![3.0]);
+    }
+
+    #[test]
+    fn merges_similar_genotypes() {
+        let mut hall_of_fame = HallOfFame::new(3).with_min_distance(0.5);
+
+        hall_of_fame.insert(entry(&[1.0, 1.0], 2.0));
+        hall_of_fame.insert(entry(&[1.2, 1.2], 1.0));
+        hall_of_fame.insert(entry(&[2.0, 2.0], 1.0));
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![2.0, 1.0]);
+        assert_eq!(hall_of_fame.entries()[1].chromosome[0], 2.0);
+    }
+
+    #[test]
+    fn update() {
+        let mut hall_of_fame = HallOfFame::new(2);
+
+        let population: Vec<_> = [[1.0, 1.0], [0.0, 0.5], [3.0, 1.0]]
+            .into_iter()
+            .map(|genes| TestIndividual::create(genes.into_iter().collect()))
+            .collect();
+
+        hall_of_fame.update(&population, 7);
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![4.0, 2.0]);
+        assert_eq!(hall_of_fame.best().unwrap().generation, 7);
+    }
+
+    #[test]
+    fn reinject() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut hall_of_fame = HallOfFame::new(2);
+
+        hall_of_fame.insert(entry(&[9.0, 9.0], 18.0));
+        hall_of_fame.insert(entry(&[8.0, 8.0], 16.0));
+        hall_of_fame.insert(entry(&[7.0], 100.0));
+
+        let mut population: Vec<_> = (0..4)
+            .map(|_| TestIndividual::create(vec![0.0, 0.0].into_iter().collect()))
+            .collect();
+
+        hall_of_fame.reinject(&mut rng, &mut population, 1, None);
+
+        let fitnesses: Vec<_> = population.iter().map(|i| i.fitness()).collect();
+        assert_eq!(fitnesses.iter().filter(|&&f| f == 18.0).count(), 1);
+        assert_eq!(fitnesses.iter().filter(|&&f| f == 0.0).count(), 3);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        hall_of_fame.insert(entry(&[0.1, -0.25, 1e-7], 2.5));
+        hall_of_fame.insert(entry(&[3.0, 4.0, 5.0], 1.0));
+
+        let mut saved = Vec::new();
+        hall_of_fame.save(&mut saved).unwrap();
+
+        let loaded = HallOfFame::load(&mut saved.as_slice(), 3).unwrap();
+
+        assert_eq!(fitnesses(&loaded), vec![2.5, 1.0]);
+
+        for (a, b) in hall_of_fame.entries().iter().zip(loaded.entries()) {
+            assert_eq!(a.chromosome, b.chromosome);
+            assert_eq!(a.generation, b.generation);
+        }
+
+        assert!(HallOfFame::load(&mut "1 2 3\n".as_bytes(), 3).is_err());
+        assert!(HallOfFame::load(&mut "# hall-of-fame v1\n1 x\n".as_bytes(), 3).is_err());
+    }
+}
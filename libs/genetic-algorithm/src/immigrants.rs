@@ -0,0 +1,288 @@
+use crate::*;
+
+// Where immigrants come from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImmigrantSource {
+    // Brand new chromosomes, with numeric genes drawn uniformly from
+    // `min..=max` - see `Gene::random_in()`
+    Random { min: f32, max: f32 },
+    // Copies of the best chromosomes found so far
+    HallOfFame,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImmigrantTrigger {
+    // Every generation
+    Always,
+    // Only when the population's `diversity()` falls below given threshold
+    LowDiversity(f32),
+}
+
+// Random-immigrant policy: replaces a fraction of each generation with
+// individuals that don't descend from it, so that new genetic material
+// keeps flowing in even after the population has converged
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Immigrants {
+    fraction: f32,
+    source: ImmigrantSource,
+    trigger: ImmigrantTrigger,
+}
+
+impl Immigrants {
+    pub fn new(fraction: f32, source: ImmigrantSource) -> Self {
+        assert!((0.0..=1.0).contains(&fraction));
+
+        Self {
+            fraction,
+            source,
+            trigger: ImmigrantTrigger::Always,
+        }
+    }
+
+    pub fn with_trigger(mut self, trigger: ImmigrantTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.fraction
+    }
+
+    pub fn source(&self) -> ImmigrantSource {
+        self.source
+    }
+
+    pub fn trigger(&self) -> ImmigrantTrigger {
+        self.trigger
+    }
+}
+
+impl<S, C, M> GeneticAlgorithm<S, C, M>
+where
+    S: SelectionMethod,
+{
+    pub fn with_immigrants(mut self, immigrants: Immigrants) -> Self {
+        self.immigrants = Some(immigrants);
+        self
+    }
+
+    // Applies the immigrant policy (if any) to a freshly evolved
+    // generation, before its individuals get evaluated - `evolve()` and its
+    // variants already do it for every generation they breed.
+    // `hall_of_fame` is only needed for `ImmigrantSource::HallOfFame`;
+    // given `genealogy`, immigrants are registered as founders.
+    //
+    // Returns how many individuals have been replaced.
+    pub fn immigrate<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &mut [I],
+        hall_of_fame: Option<&HallOfFame<G>>,
+        mut genealogy: Option<&mut Genealogy>,
+    ) -> usize
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        let Some(immigrants) = &self.immigrants else {
+            return 0;
+        };
+
+        if population.is_empty() {
+            return 0;
+        }
+
+        if let ImmigrantTrigger::LowDiversity(threshold) = immigrants.trigger
+            && diversity(population) >= threshold
+        {
+            return 0;
+        }
+
+        let count = (immigrants.fraction * population.len() as f32).round() as usize;
+
+        match immigrants.source {
+            ImmigrantSource::Random { min, max } => {
+                let slots = rand::seq::index::sample(rng, population.len(), count);
+
+                for slot in slots {
+                    let native = population[slot].chromosome();
+
+                    let mut chromosome: Chromosome<G> = native
+                        .iter()
+                        .map(|gene| gene.random_in(rng, min, max))
+                        .collect();
+
+                    chromosome.inherit_bounds(native);
+                    chromosome.enforce_bounds(rng);
+
+                    let mut immigrant = I::create(chromosome);
+
+                    if let Some(genealogy) = genealogy.as_deref_mut() {
+                        genealogy.replace(&population[slot], &mut immigrant);
+                    }
+
+                    population[slot] = immigrant;
+                }
+
+                count
+            }
+
+            ImmigrantSource::HallOfFame => {
+                let Some(hall_of_fame) = hall_of_fame else {
+                    return 0;
+                };
+
+                let genes = population[0].chromosome().len();
+                let available = hall_of_fame
+                    .entries()
+                    .iter()
+                    .filter(|entry| entry.chromosome.len() == genes)
+                    .count();
+
+                hall_of_fame.reinject(rng, population, count, genealogy);
+                count.min(available)
+            }
+        }
+    }
+}
+
+// Average distance between two genes at the same locus of two different
+// individuals - zero means all chromosomes are the same
+pub fn diversity<I, G>(population: &[I]) -> f32
+where
+    I: Individual<G>,
+    G: Gene,
+{
+    let mut sum = 0.0;
+    let mut count = 0;
+
+    for (idx, a) in population.iter().enumerate() {
+        for b in &population[idx + 1..] {
+            for (a, b) in a.chromosome().iter().zip(b.chromosome().iter()) {
+                sum += a.distance(b);
+                count += 1;
+            }
+        }
+    }
+
+    if count == 0 { 0.0 } else { sum / count as f32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn ga() -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        )
+    }
+
+    fn clones(count: usize) -> Vec<TestIndividual> {
+        (0..count)
+            .map(|_| TestIndividual::create(vec![5.0, 5.0, 5.0].into_iter().collect()))
+            .collect()
+    }
+
+    fn immigrants(population: &[TestIndividual]) -> usize {
+        population
+            .iter()
+            .filter(|individual| individual.fitness() != 15.0)
+            .count()
+    }
+
+    #[test]
+    fn diversity() {
+        assert_eq!(super::diversity(&clones(4)), 0.0);
+
+        let population: Vec<_> = [[0.0, 0.0], [1.0, 2.0], [2.0, 0.0]]
+            .into_iter()
+            .map(|genes| TestIndividual::create(genes.into_iter().collect()))
+            .collect();
+
+        // (1 + 2) + (2 + 0) + (1 + 2) over 6 pairs of genes
+        assert_eq!(super::diversity(&population), 8.0 / 6.0);
+    }
+
+    #[test]
+    fn random_immigrants() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = clones(10);
+
+        let ga = ga().with_immigrants(Immigrants::new(
+            0.3,
+            ImmigrantSource::Random {
+                min: -1.0,
+                max: 1.0,
+            },
+        ));
+
+        assert_eq!(ga.immigrate(&mut rng, &mut population, None, None), 3);
+        assert_eq!(immigrants(&population), 3);
+
+        for individual in &population {
+            for &gene in individual.chromosome().iter() {
+                assert!(gene == 5.0 || (-1.0..=1.0).contains(&gene));
+            }
+        }
+    }
+
+    #[test]
+    fn immigrants_from_hall_of_fame() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = clones(10);
+        let mut hall_of_fame = HallOfFame::new(5);
+
+        hall_of_fame.update(
+            &[TestIndividual::create(vec![9.0; 3].into_iter().collect())],
+            0,
+        );
+
+        let ga = ga().with_immigrants(Immigrants::new(0.5, ImmigrantSource::HallOfFame));
+
+        assert_eq!(ga.immigrate(&mut rng, &mut population, None, None), 0);
+        assert_eq!(
+            ga.immigrate(&mut rng, &mut population, Some(&hall_of_fame), None),
+            1
+        );
+        assert_eq!(immigrants(&population), 1);
+    }
+
+    #[test]
+    fn immigrants_on_low_diversity() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = ga().with_immigrants(
+            Immigrants::new(
+                0.5,
+                ImmigrantSource::Random {
+                    min: -1.0,
+                    max: 1.0,
+                },
+            )
+            .with_trigger(ImmigrantTrigger::LowDiversity(0.1)),
+        );
+
+        let mut diverse: Vec<_> = (0..4)
+            .map(|n| TestIndividual::create(vec![n as f32; 3].into_iter().collect()))
+            .collect();
+
+        assert_eq!(ga.immigrate(&mut rng, &mut diverse, None, None), 0);
+
+        let mut converged = clones(4);
+        assert_eq!(ga.immigrate(&mut rng, &mut converged, None, None), 2);
+        assert!(super::diversity(&converged) > 0.0);
+    }
+
+    #[test]
+    fn no_policy() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = clones(4);
+
+        assert_eq!(ga().immigrate(&mut rng, &mut population, None, None), 0);
+    }
+}
@@ -1,9 +1,17 @@
 use crate::*;
 
-pub trait Individual {
-    fn create(chromosome: Chromosome) -> Self;
-    fn chromosome(&self) -> &Chromosome;
+pub trait Individual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
     fn fitness(&self) -> f32;
+
+    // Individuals that want to take part in genealogy tracking have to
+    // store the lineage they are given - see `Genealogy`
+    fn lineage(&self) -> Option<&Lineage> {
+        None
+    }
+
+    fn set_lineage(&mut self, _lineage: Lineage) {}
 }
 
 #[cfg(test)]
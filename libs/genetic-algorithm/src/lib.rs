@@ -1,17 +1,38 @@
+mod bounds;
+mod breeding;
 mod chromosome;
+mod combinator;
 mod crossover;
+mod driver;
+mod gene;
+mod genealogy;
+mod genome;
+mod hall_of_fame;
+mod immigrants;
 mod individual;
 mod mutation;
+mod novelty;
 mod selection;
 mod statistics;
+mod steady_state;
 
 pub use self::{
+    bounds::*,
     chromosome::*,
+    combinator::*,
     crossover::*,
+    driver::*,
+    gene::*,
+    genealogy::*,
+    genome::*,
+    hall_of_fame::*,
+    immigrants::*,
     individual::*,
     mutation::*,
+    novelty::*,
     selection::*,
     statistics::*,
+    steady_state::*,
 };
 
 use rand::seq::SliceRandom;
@@ -21,39 +42,169 @@ pub struct GeneticAlgorithm<S, C, M> {
     selection_method: S,
     crossover_method: C,
     mutation_method: M,
+    immigrants: Option<Immigrants>,
 }
 
 impl<S, C, M> GeneticAlgorithm<S, C, M>
 where
     S: SelectionMethod,
-    C: CrossoverMethod,
-    M: MutationMethod,
 {
     pub fn new(selection_method: S, crossover_method: C, mutation_method: M) -> Self {
         Self {
             selection_method,
             crossover_method,
             mutation_method,
+            immigrants: None,
         }
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    pub fn evolve<I, G>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
     where
-        I: Individual,
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.evolve_inner(rng, population, population.len(), None, None)
+    }
+
+    // Same as `evolve()`, but breeds `size` children instead of as many as
+    // there were parents - so that the population can grow or shrink
+    pub fn evolve_to<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        size: usize,
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.evolve_inner(rng, population, size, None, None)
+    }
+
+    // Same as `evolve()`, but also assigns every child a `Lineage` and
+    // records it (together with the parents' fitness) in `genealogy`
+    pub fn evolve_with_genealogy<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        genealogy: &mut Genealogy,
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.evolve_inner(rng, population, population.len(), Some(genealogy), None)
+    }
+
+    // Combination of `evolve_to()` and `evolve_with_genealogy()`
+    pub fn evolve_to_with_genealogy<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        size: usize,
+        genealogy: &mut Genealogy,
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.evolve_inner(rng, population, size, Some(genealogy), None)
+    }
+
+    // Same as `evolve_to_with_genealogy()`, but also lets the immigrant
+    // policy draw from `hall_of_fame`, see `ImmigrantSource::HallOfFame`
+    pub fn evolve_to_with_hall_of_fame<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        size: usize,
+        genealogy: &mut Genealogy,
+        hall_of_fame: &HallOfFame<G>,
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.evolve_inner(rng, population, size, Some(genealogy), Some(hall_of_fame))
+    }
+
+    fn evolve_inner<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        size: usize,
+        mut genealogy: Option<&mut Genealogy>,
+        hall_of_fame: Option<&HallOfFame<G>>,
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
     {
         assert!(!population.is_empty());
-        let new_population = (0..population.len())
+
+        if let Some(genealogy) = genealogy.as_deref_mut() {
+            for individual in population {
+                if let Some(lineage) = individual.lineage() {
+                    genealogy.record_fitness(lineage.id, individual.fitness());
+                }
+            }
+
+            genealogy.next_generation();
+        }
+
+        let mut new_population: Vec<I> = (0..size)
             .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-                let parent_b = self.selection_method.select(rng, population).chromosome();
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+                let parent_a = self.selection_method.select(rng, population);
+                let parent_b = self.selection_method.select(rng, population);
+                let mut child = self.crossover_method.crossover(
+                    rng,
+                    parent_a.chromosome(),
+                    parent_b.chromosome(),
+                );
+
+                let Some(genealogy) = genealogy.as_deref_mut() else {
+                    self.mutation_method.mutate(rng, &mut child);
+                    return I::create(child);
+                };
+
+                let crossed_over = child.clone();
                 self.mutation_method.mutate(rng, &mut child);
 
-                I::create(child)
+                let mut parents: Vec<_> = [parent_a, parent_b]
+                    .iter()
+                    .filter_map(|parent| parent.lineage())
+                    .map(|lineage| lineage.id)
+                    .collect();
+
+                parents.dedup();
+
+                let mutation = MutationRecord::between(&crossed_over, &child);
+                let mut child = I::create(child);
+                child.set_lineage(genealogy.child(parents, mutation));
+                child
             })
             .collect();
 
-        let stats = Statistics::new(population);
+        self.immigrate(rng, &mut new_population, hall_of_fame, genealogy);
+
+        let mut stats = Statistics::new(population);
+        stats.bound_hits = new_population
+            .iter()
+            .map(|child| child.chromosome().bound_hits())
+            .sum();
+
         (new_population, stats)
     }
 }
@@ -97,5 +248,73 @@ mod tests {
 
         assert_eq!(population, expected_population);
     }
-}
 
+    #[test]
+    fn genetic_algorithm_with_bool_genes() {
+        // Classic "OneMax" problem - the more `true`s, the better
+        struct Bits(Chromosome<bool>);
+
+        impl Individual<bool> for Bits {
+            fn create(chromosome: Chromosome<bool>) -> Self {
+                Self(chromosome)
+            }
+
+            fn chromosome(&self) -> &Chromosome<bool> {
+                &self.0
+            }
+
+            fn fitness(&self) -> f32 {
+                1.0 + self.0.iter().filter(|&&bit| bit).count() as f32
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            BitFlipMutation::new(0.01),
+        );
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| Bits((0..32).map(|_| rng.gen_bool(0.5)).collect()))
+            .collect();
+
+        let (_, initial) = ga.evolve(&mut rng, &population);
+
+        for _ in 0..30 {
+            (population, _) = ga.evolve(&mut rng, &population);
+        }
+
+        let (_, last) = ga.evolve(&mut rng, &population);
+
+        assert!(last.avg_fitness > initial.avg_fitness + 5.0);
+    }
+
+    #[test]
+    fn evolve_to() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population: Vec<_> = (1..=4)
+            .map(|n| TestIndividual::create(vec![n as f32; 3].into_iter().collect()))
+            .collect();
+
+        let (grown, stats) = ga.evolve_to(&mut rng, &population, 10);
+        assert_eq!(grown.len(), 10);
+        assert_eq!(stats.max_fitness, 12.0);
+
+        let (shrunk, _) = ga.evolve_to(&mut rng, &grown, 3);
+        assert_eq!(shrunk.len(), 3);
+
+        let mut genealogy = Genealogy::new();
+        let (children, _) = ga.evolve_to_with_genealogy(&mut rng, &shrunk, 6, &mut genealogy);
+        assert_eq!(children.len(), 6);
+        assert_eq!(genealogy.len(), 6);
+    }
+}
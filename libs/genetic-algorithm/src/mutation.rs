@@ -1,8 +1,23 @@
+mod bit_flip;
 mod gaussian;
+mod reset;
 
+pub use self::bit_flip::*;
 pub use self::gaussian::*;
+pub use self::reset::*;
 use crate::*;
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+// Implementations should keep the child within its bounds - see
+// `Chromosome::enforce_bounds()`
+pub trait MutationMethod<G = f32> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+}
+
+impl<G, M> MutationMethod<G> for Box<M>
+where
+    M: MutationMethod<G> + ?Sized,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        (**self).mutate(rng, child)
+    }
 }
@@ -0,0 +1,85 @@
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        }
+
+        child.enforce_bounds(rng);
+    }
+}
+
+// Only flips boolean genes, leaving the numeric ones intact
+impl MutationMethod<MixedGene> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<MixedGene>) {
+        for gene in child.iter_mut() {
+            if let MixedGene::Bool(value) = gene
+                && rng.gen_bool(self.chance as _)
+            {
+                *value = !*value;
+            }
+        }
+
+        child.enforce_bounds(rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<bool> {
+        let mut child: Chromosome<bool> = [true, false, true, false, true].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        BitFlipMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), vec![true, false, true, false, true]);
+    }
+
+    #[test]
+    fn given_max_chance_flips_every_gene() {
+        assert_eq!(actual(1.0), vec![false, true, false, true, false]);
+    }
+
+    #[test]
+    fn leaves_numeric_genes_intact() {
+        let mut child: Chromosome<MixedGene> = [
+            MixedGene::Float(0.5),
+            MixedGene::Bool(true),
+            MixedGene::Integer(BoundedInteger::new(2, 0, 4)),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        BitFlipMutation::new(1.0).mutate(&mut rng, &mut child);
+
+        assert_eq!(child[0], MixedGene::Float(0.5));
+        assert_eq!(child[1], MixedGene::Bool(false));
+        assert_eq!(child[2], MixedGene::Integer(BoundedInteger::new(2, 0, 4)));
+    }
+}
@@ -8,21 +8,96 @@ pub struct GaussianMutation {
 
 impl GaussianMutation {
     pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+        assert!((0.0..=1.0).contains(&chance));
 
         Self { chance, coeff }
     }
 }
 
+impl GaussianMutation {
+    // Returns by how much a gene should change, if it should change at all
+    fn delta(&self, rng: &mut dyn RngCore) -> Option<f32> {
+        let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+
+        if rng.gen_bool(self.chance as _) {
+            Some(sign * self.coeff * rng.r#gen::<f32>())
+        } else {
+            None
+        }
+    }
+
+    // Rounds `delta` up or down at random, with chances proportional to
+    // how close it is to either integer - so that even steps shorter than
+    // one move the gene once in a while
+    fn integer_step(rng: &mut dyn RngCore, delta: f32) -> i32 {
+        let whole = delta.trunc();
+        let step = whole as i32;
+
+        if rng.gen_bool((delta - whole).abs() as _) {
+            step + delta.signum() as i32
+        } else {
+            step
+        }
+    }
+}
+
 impl MutationMethod for GaussianMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
         for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+            if let Some(delta) = self.delta(rng) {
+                *gene += delta;
+            }
+        }
+
+        child.enforce_bounds(rng);
+    }
+}
 
-            if rng.gen_bool(self.chance as _) {
-                *gene += sign * self.coeff * rng.r#gen::<f32>();
+impl MutationMethod<f64> for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<f64>) {
+        for gene in child.iter_mut() {
+            if let Some(delta) = self.delta(rng) {
+                *gene += delta as f64;
+            }
+        }
+
+        child.enforce_bounds(rng);
+    }
+}
+
+// For integers `coeff` is the largest step a gene can take; steps are
+// rounded stochastically (see `integer_step()`) and clamped to the gene's
+// bounds
+impl MutationMethod<BoundedInteger> for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<BoundedInteger>) {
+        for gene in child.iter_mut() {
+            if let Some(delta) = self.delta(rng) {
+                gene.set(gene.value() + Self::integer_step(rng, delta));
             }
         }
+
+        child.enforce_bounds(rng);
+    }
+}
+
+// Numeric genes are nudged as above, boolean ones get flipped
+impl MutationMethod<MixedGene> for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<MixedGene>) {
+        for gene in child.iter_mut() {
+            let Some(delta) = self.delta(rng) else {
+                continue;
+            };
+
+            match gene {
+                MixedGene::Float(value) => *value += delta,
+                MixedGene::Integer(value) => {
+                    value.set(value.value() + Self::integer_step(rng, delta))
+                }
+                MixedGene::Bool(value) => *value = !*value,
+            }
+        }
+
+        child.enforce_bounds(rng);
     }
 }
 
@@ -130,4 +205,77 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn mutates_doubles() {
+        let mut child: Chromosome<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        GaussianMutation::new(0.5, 0.5).mutate(&mut rng, &mut child);
+
+        let doubles: Vec<_> = child.into_iter().map(|gene| gene as f32).collect();
+        let floats = actual(0.5, 0.5);
+
+        approx::assert_relative_eq!(doubles.as_slice(), floats.as_slice());
+    }
+
+    #[test]
+    fn keeps_integers_within_bounds() {
+        let mut child: Chromosome<BoundedInteger> =
+            (0..100).map(|n| BoundedInteger::new(n % 4, 0, 3)).collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        GaussianMutation::new(1.0, 5.0).mutate(&mut rng, &mut child);
+
+        assert!(child.iter().all(|gene| (0..=3).contains(&gene.value())));
+        assert!(
+            child
+                .iter()
+                .enumerate()
+                .any(|(n, gene)| gene.value() != n as i32 % 4)
+        );
+    }
+
+    #[test]
+    fn mutates_integers_by_small_steps() {
+        let mut child: Chromosome<BoundedInteger> =
+            (0..100).map(|_| BoundedInteger::new(5, 0, 10)).collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        GaussianMutation::new(1.0, 0.3).mutate(&mut rng, &mut child);
+
+        assert!(child.iter().all(|gene| (4..=6).contains(&gene.value())));
+        assert!(child.iter().any(|gene| gene.value() == 4));
+        assert!(child.iter().any(|gene| gene.value() == 6));
+
+        let mut child: Chromosome<MixedGene> = (0..100)
+            .map(|_| MixedGene::Integer(BoundedInteger::new(5, 0, 10)))
+            .collect();
+
+        GaussianMutation::new(1.0, 0.3).mutate(&mut rng, &mut child);
+
+        assert!(
+            child
+                .iter()
+                .any(|gene| !matches!(gene, MixedGene::Integer(value) if value.value() == 5))
+        );
+    }
+
+    #[test]
+    fn mutates_mixed_genes() {
+        let genes = [
+            MixedGene::Float(1.0),
+            MixedGene::Integer(BoundedInteger::new(5, 0, 10)),
+            MixedGene::Bool(false),
+        ];
+
+        let mut child: Chromosome<MixedGene> = genes.iter().copied().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        GaussianMutation::new(1.0, 2.0).mutate(&mut rng, &mut child);
+
+        assert!(matches!(child[0], MixedGene::Float(value) if value != 1.0));
+        assert!(matches!(child[1], MixedGene::Integer(_)));
+        assert_eq!(child[2], MixedGene::Bool(true));
+    }
 }
@@ -0,0 +1,60 @@
+use crate::*;
+
+// Replaces genes with brand new random ones, drawn from `min..=max` - a
+// much bigger jump than `GaussianMutation` ever makes
+#[derive(Clone, Debug)]
+pub struct ResetMutation {
+    chance: f32,
+    min: f32,
+    max: f32,
+}
+
+impl ResetMutation {
+    pub fn new(chance: f32, min: f32, max: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(min <= max);
+
+        Self { chance, min, max }
+    }
+}
+
+impl<G> MutationMethod<G> for ResetMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = gene.random_in(rng, self.min, self.max);
+            }
+        }
+
+        child.enforce_bounds(rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<f32> {
+        let mut child: Chromosome = vec![5.0, 6.0, 7.0, 8.0, 9.0].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        ResetMutation::new(chance, -1.0, 1.0).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), vec![5.0, 6.0, 7.0, 8.0, 9.0]);
+    }
+
+    #[test]
+    fn given_max_chance_resets_every_gene() {
+        assert!(actual(1.0).iter().all(|gene| (-1.0..=1.0).contains(gene)));
+    }
+}
@@ -0,0 +1,221 @@
+use crate::*;
+use std::collections::VecDeque;
+
+// What an individual did (as opposed to what it's made of) - e.g. where it
+// ended up and how it got there; novelty search rewards individuals that
+// behave differently from everyone seen so far
+#[derive(Clone, Debug, PartialEq)]
+pub struct Behaviour {
+    features: Vec<f32>,
+}
+
+impl Behaviour {
+    pub fn new(features: Vec<f32>) -> Self {
+        Self { features }
+    }
+
+    pub fn features(&self) -> &[f32] {
+        &self.features
+    }
+
+    // Euclidean distance between two descriptors
+    pub fn distance(&self, other: &Self) -> f32 {
+        assert_eq!(self.features.len(), other.features.len());
+
+        self.features
+            .iter()
+            .zip(&other.features)
+            .map(|(a, b)| a.distance(b).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+
+// Behaviours that were novel at some point, so that the search doesn't
+// keep going back and forth between the same few strategies.
+//
+// Once full, the oldest behaviours make room for the new ones.
+#[derive(Clone, Debug)]
+pub struct NoveltyArchive {
+    neighbours: usize,
+    capacity: usize,
+    threshold: f32,
+    behaviours: VecDeque<Behaviour>,
+}
+
+impl NoveltyArchive {
+    // `neighbours` is the `k` of the k-nearest-neighbours novelty metric
+    pub fn new(neighbours: usize, capacity: usize) -> Self {
+        assert!(neighbours > 0);
+
+        Self {
+            neighbours,
+            capacity,
+            threshold: 0.0,
+            behaviours: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    // Only behaviours more novel than this get archived
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        assert!(threshold >= 0.0);
+
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviours.is_empty()
+    }
+
+    pub fn behaviours(&self) -> impl Iterator<Item = &Behaviour> {
+        self.behaviours.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.behaviours.clear();
+    }
+
+    // Returns novelty of each behaviour of the current population - i.e.
+    // its average distance to the `neighbours` closest behaviours among
+    // the rest of the population and the archive - and then archives the
+    // ones that were novel enough
+    pub fn score(&mut self, population: &[Behaviour]) -> Vec<f32> {
+        let novelty: Vec<_> = population
+            .iter()
+            .enumerate()
+            .map(|(idx, behaviour)| {
+                let mut distances: Vec<_> = population
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_idx, _)| *other_idx != idx)
+                    .map(|(_, other)| other)
+                    .chain(&self.behaviours)
+                    .map(|other| behaviour.distance(other))
+                    .collect();
+
+                if distances.is_empty() {
+                    return 0.0;
+                }
+
+                distances.sort_by(|a, b| a.total_cmp(b));
+                distances.truncate(self.neighbours);
+                distances.iter().sum::<f32>() / distances.len() as f32
+            })
+            .collect();
+
+        for (behaviour, &novelty) in population.iter().zip(&novelty) {
+            if novelty > self.threshold {
+                self.push(behaviour.clone());
+            }
+        }
+
+        novelty
+    }
+
+    fn push(&mut self, behaviour: Behaviour) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.behaviours.len() == self.capacity {
+            self.behaviours.pop_front();
+        }
+
+        self.behaviours.push_back(behaviour);
+    }
+}
+
+// Blends fitness and novelty into a single score: with weight of zero it's
+// pure fitness, with weight of one - pure novelty search.
+//
+// Both scores are taken as they are, so it's up to the caller to pick a
+// weight that accounts for their scales.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedObjective {
+    novelty_weight: f32,
+}
+
+impl WeightedObjective {
+    pub fn new(novelty_weight: f32) -> Self {
+        assert!((0.0..=1.0).contains(&novelty_weight));
+
+        Self { novelty_weight }
+    }
+
+    pub fn novelty_weight(&self) -> f32 {
+        self.novelty_weight
+    }
+
+    pub fn score(&self, fitness: f32, novelty: f32) -> f32 {
+        (1.0 - self.novelty_weight) * fitness + self.novelty_weight * novelty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn behaviours(points: &[[f32; 2]]) -> Vec<Behaviour> {
+        points
+            .iter()
+            .map(|point| Behaviour::new(point.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn distance() {
+        let a = Behaviour::new(vec![0.0, 0.0]);
+        let b = Behaviour::new(vec![3.0, 4.0]);
+
+        assert_eq!(a.distance(&b), 5.0);
+    }
+
+    #[test]
+    fn scores_by_nearest_neighbours() {
+        let mut archive = NoveltyArchive::new(2, 10).with_threshold(f32::INFINITY);
+
+        let novelty = archive.score(&behaviours(&[
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [2.0, 0.0],
+            [10.0, 0.0],
+        ]));
+
+        assert_eq!(novelty, vec![1.5, 1.0, 1.5, 8.5]);
+        assert!(archive.is_empty());
+    }
+
+    #[test]
+    fn archived_behaviours_count_as_neighbours() {
+        let mut archive = NoveltyArchive::new(1, 10).with_threshold(6.0);
+
+        archive.score(&behaviours(&[[0.0, 0.0], [5.0, 0.0], [20.0, 0.0]]));
+        assert_eq!(archive.len(), 1);
+
+        // The closest one is the archived [20, 0], not its sibling
+        let novelty = archive.score(&behaviours(&[[21.0, 0.0], [30.0, 0.0]]));
+        assert_eq!(novelty, vec![1.0, 9.0]);
+    }
+
+    #[test]
+    fn forgets_oldest_behaviours() {
+        let mut archive = NoveltyArchive::new(1, 2);
+
+        archive.score(&behaviours(&[[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]]));
+
+        let archived: Vec<_> = archive.behaviours().map(|b| b.features()[0]).collect();
+        assert_eq!(archived, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn weighted_objective() {
+        assert_eq!(WeightedObjective::new(0.0).score(4.0, 10.0), 4.0);
+        assert_eq!(WeightedObjective::new(1.0).score(4.0, 10.0), 10.0);
+        assert_eq!(WeightedObjective::new(0.25).score(4.0, 8.0), 5.0);
+    }
+}
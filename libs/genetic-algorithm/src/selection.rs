@@ -6,7 +6,7 @@ pub use self::rank_based::*;
 use crate::*;
 
 pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
     where
-        I: Individual;
+        I: Individual<G>;
 }
@@ -5,9 +5,9 @@ pub struct RankBasedSelection;
 
 
 impl SelectionMethod for RankBasedSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
     where
-        I: Individual,
+        I: Individual<G>,
     {
         assert!(!population.is_empty(), "got an empty population");
 
@@ -6,12 +6,16 @@ pub struct Statistics {
     pub max_fitness: f32,
     pub avg_fitness: f32,
     pub median_fitness: f32,
+    // How many genes of the offspring bred along with these statistics had
+    // to be brought back within their `Bounds` - always zero for
+    // `Statistics::new()`, which doesn't breed anything
+    pub bound_hits: usize,
 }
 
 impl Statistics {
-    pub fn new<I>(population: &[I]) -> Self
+    pub fn new<I, G>(population: &[I]) -> Self
     where
-        I: Individual,
+        I: Individual<G>,
     {
         assert!(!population.is_empty());
 
@@ -42,6 +46,7 @@ impl Statistics {
             max_fitness,
             avg_fitness: sum_fitness / (population.len() as f32),
             median_fitness,
+            bound_hits: 0,
         }
     }
 }
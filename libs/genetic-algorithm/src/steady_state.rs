@@ -0,0 +1,316 @@
+use crate::*;
+
+// Which individuals make room for the children bred by
+// `GeneticAlgorithm::evolve_steady_state()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplacementPolicy {
+    // The least fit individuals
+    Worst,
+    // The individuals that have been in the population for the longest
+    Oldest,
+    // Uniformly chosen individuals
+    Random,
+    // Each child competes with the more similar of its two parents and
+    // replaces it only if it's fitter - which keeps the population from
+    // converging onto a single genotype.
+    //
+    // Child's fitness is whatever `Individual::fitness()` returns right
+    // after `Individual::create()`, so this policy only makes sense for
+    // individuals that get evaluated upon creation.
+    DeterministicCrowding,
+}
+
+// State of a steady-state run: instead of replacing the whole population
+// at once, each call to `GeneticAlgorithm::evolve_steady_state()` breeds
+// just `offspring` children and puts them in place of the individuals
+// chosen by `policy`
+#[derive(Clone, Debug)]
+pub struct SteadyState {
+    policy: ReplacementPolicy,
+    offspring: usize,
+    step: u64,
+    // When each slot of the population was last replaced
+    births: Vec<u64>,
+}
+
+impl SteadyState {
+    pub fn new(policy: ReplacementPolicy, offspring: usize) -> Self {
+        assert!(offspring > 0);
+
+        Self {
+            policy,
+            offspring,
+            step: 0,
+            births: Vec::new(),
+        }
+    }
+
+    pub fn policy(&self) -> ReplacementPolicy {
+        self.policy
+    }
+
+    pub fn offspring(&self) -> usize {
+        self.offspring
+    }
+
+    // How many times the population has been evolved so far
+    pub fn steps(&self) -> u64 {
+        self.step
+    }
+
+    // Number of steps given slot has survived without being replaced
+    pub fn age(&self, slot: usize) -> u64 {
+        self.step - self.births.get(slot).copied().unwrap_or(0)
+    }
+}
+
+impl<S, C, M> GeneticAlgorithm<S, C, M>
+where
+    S: SelectionMethod,
+{
+    // Breeds `state.offspring()` children and replaces members of
+    // `population` in-place, according to `state.policy()`.
+    //
+    // Returns indices of the replaced individuals (so that the caller can
+    // e.g. spawn new agents in their place), together with statistics of
+    // the population as it was before the replacement (except for
+    // `bound_hits`, which describe the children).
+    pub fn evolve_steady_state<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &mut [I],
+        state: &mut SteadyState,
+    ) -> (Vec<usize>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        assert!(!population.is_empty());
+
+        let mut stats = Statistics::new(population);
+
+        let count = state.offspring.min(population.len());
+
+        state.births.resize(population.len(), 0);
+        state.step += 1;
+
+        let mut children = Vec::with_capacity(count);
+        let mut parents = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let parent_a = self.selection_method.select(rng, population);
+            let parent_b = self.selection_method.select(rng, population);
+
+            let mut child =
+                self.crossover_method
+                    .crossover(rng, parent_a.chromosome(), parent_b.chromosome());
+
+            self.mutation_method.mutate(rng, &mut child);
+            stats.bound_hits += child.bound_hits();
+
+            children.push(I::create(child));
+            parents.push([slot_of(population, parent_a), slot_of(population, parent_b)]);
+        }
+
+        // Slot each child should go into, if any
+        let slots: Vec<Option<usize>> = match state.policy {
+            ReplacementPolicy::Worst => {
+                let mut slots: Vec<_> = (0..population.len()).collect();
+                slots.sort_by(|&a, &b| population[a].fitness().total_cmp(&population[b].fitness()));
+                slots.into_iter().take(count).map(Some).collect()
+            }
+
+            ReplacementPolicy::Oldest => {
+                let mut slots: Vec<_> = (0..population.len()).collect();
+                slots.sort_by_key(|&slot| state.births[slot]);
+                slots.into_iter().take(count).map(Some).collect()
+            }
+
+            ReplacementPolicy::Random => rand::seq::index::sample(rng, population.len(), count)
+                .into_iter()
+                .map(Some)
+                .collect(),
+
+            ReplacementPolicy::DeterministicCrowding => {
+                let mut slots = Vec::with_capacity(count);
+
+                for (child, [parent_a, parent_b]) in children.iter().zip(&parents) {
+                    let distance_a = child
+                        .chromosome()
+                        .distance(population[*parent_a].chromosome());
+                    let distance_b = child
+                        .chromosome()
+                        .distance(population[*parent_b].chromosome());
+
+                    let rival = if distance_a <= distance_b {
+                        *parent_a
+                    } else {
+                        *parent_b
+                    };
+
+                    // Two children might compete for the same parent - the
+                    // first one to win takes the slot
+                    let wins = child.fitness() > population[rival].fitness()
+                        && !slots.contains(&Some(rival));
+
+                    slots.push(wins.then_some(rival));
+                }
+
+                slots
+            }
+        };
+
+        let mut replaced = Vec::with_capacity(count);
+
+        for (slot, child) in slots.into_iter().zip(children) {
+            let Some(slot) = slot else {
+                continue;
+            };
+
+            population[slot] = child;
+            state.births[slot] = state.step;
+            replaced.push(slot);
+        }
+
+        (replaced, stats)
+    }
+}
+
+fn slot_of<I>(population: &[I], individual: &I) -> usize {
+    population
+        .iter()
+        .position(|other| std::ptr::eq(other, individual))
+        .expect("selected individual should come from the population")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::Arc;
+
+    fn ga() -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        )
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        (1..=6)
+            .map(|n| TestIndividual::create(vec![n as f32; 2].into_iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn replaces_worst() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = population();
+        let mut state = SteadyState::new(ReplacementPolicy::Worst, 2);
+
+        let (mut replaced, stats) = ga().evolve_steady_state(&mut rng, &mut population, &mut state);
+        replaced.sort();
+
+        assert_eq!(replaced, vec![0, 1]);
+        assert_eq!(stats.min_fitness, 2.0);
+        assert_eq!(population[5].fitness(), 12.0);
+    }
+
+    #[test]
+    fn replaces_oldest() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = population();
+        let mut state = SteadyState::new(ReplacementPolicy::Oldest, 4);
+        let ga = ga();
+
+        let (first, _) = ga.evolve_steady_state(&mut rng, &mut population, &mut state);
+        let (second, _) = ga.evolve_steady_state(&mut rng, &mut population, &mut state);
+
+        assert_eq!(first, vec![0, 1, 2, 3]);
+        assert_eq!(second, vec![4, 5, 0, 1]);
+        assert_eq!(state.age(2), 1);
+        assert_eq!(state.age(4), 0);
+    }
+
+    #[test]
+    fn replaces_random() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = population();
+        let mut state = SteadyState::new(ReplacementPolicy::Random, 3);
+
+        let (mut replaced, _) = ga().evolve_steady_state(&mut rng, &mut population, &mut state);
+        replaced.sort();
+        replaced.dedup();
+
+        assert_eq!(replaced.len(), 3);
+        assert_eq!(state.steps(), 1);
+    }
+
+    #[test]
+    fn bound_hits_describe_children() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut state = SteadyState::new(ReplacementPolicy::Worst, 2);
+        let bounds = Arc::new(Bounds::global(0.0, 1.0, BoundPolicy::Clamp));
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(1.0, 10.0),
+        );
+
+        let mut population: Vec<_> = population()
+            .into_iter()
+            .map(|individual| {
+                TestIndividual::create(individual.chromosome().clone().with_bounds(bounds.clone()))
+            })
+            .collect();
+
+        for _ in 0..2 {
+            let (replaced, stats) = ga.evolve_steady_state(&mut rng, &mut population, &mut state);
+
+            let children_hits: usize = replaced
+                .iter()
+                .map(|slot| population[*slot].chromosome().bound_hits())
+                .sum();
+
+            assert_eq!(replaced.len(), 2);
+            assert!(children_hits > 0);
+            assert_eq!(stats.bound_hits, children_hits);
+        }
+    }
+
+    #[test]
+    fn deterministic_crowding_keeps_fitter_parents() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut state = SteadyState::new(ReplacementPolicy::DeterministicCrowding, 4);
+
+        // Without mutation children of identical parents are just their
+        // copies, so none of them is fitter
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(1.0, 0.0),
+        );
+
+        let mut clones: Vec<_> = (0..4)
+            .map(|_| TestIndividual::create(vec![1.0, 1.0].into_iter().collect()))
+            .collect();
+
+        let (replaced, _) = ga.evolve_steady_state(&mut rng, &mut clones, &mut state);
+        assert!(replaced.is_empty());
+
+        let mut population = population();
+        let ga = self::ga();
+
+        for _ in 0..50 {
+            ga.evolve_steady_state(&mut rng, &mut population, &mut state);
+        }
+
+        let stats = Statistics::new(&population);
+        assert!(stats.min_fitness >= 2.0);
+    }
+}
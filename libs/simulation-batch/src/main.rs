@@ -1,11 +1,15 @@
-use lib_simulation::Simulation;
+use lib_genetic_algorithm::{Genealogy, Genome, GenomeError, HallOfFame};
+use lib_simulation::{
+    BodyGenes, EyeGenes, Metabolism, PREDATOR_SPECIES, PREY_SPECIES, Simulation, SimulationConfig,
+};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
 use std::env;
 use std::fmt::Write as FmtWrite;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufWriter, Write};
+use std::path::Path;
 use std::process;
 
 #[derive(Debug, Clone)]
@@ -14,6 +18,12 @@ struct Config {
     generations: u32,
     seed: u64,
     out: String,
+    pedigree_dir: Option<String>,
+    hall_of_fame_dir: Option<String>,
+    genome_dir: Option<String>,
+    import_genomes: Vec<String>,
+    evolve_eyes: bool,
+    evolve_bodies: bool,
 }
 
 impl Default for Config {
@@ -23,14 +33,28 @@ impl Default for Config {
             generations: 100,
             seed: 42,
             out: "results.csv".to_string(),
+            pedigree_dir: None,
+            hall_of_fame_dir: None,
+            genome_dir: None,
+            import_genomes: Vec::new(),
+            evolve_eyes: false,
+            evolve_bodies: false,
         }
     }
 }
 
 fn print_help(program: &str) {
     eprintln!(
-        "Usage: {program} [--runs N] [--gens N] [--seed N] [--out PATH]\n\
-         Defaults: --runs 50 --gens 100 --seed 42 --out results.csv"
+        "Usage: {program} [--runs N] [--gens N] [--seed N] [--out PATH] [--pedigree-dir DIR]\n\
+         \x20      [--hall-of-fame-dir DIR] [--genome-dir DIR] [--import-genome PATH]...\n\
+         \x20      [--evolve-eyes] [--evolve-bodies]\n\
+         Defaults: --runs 50 --gens 100 --seed 42 --out results.csv\n\
+         --pedigree-dir writes each run's pedigree of every species as GraphViz DOT and CSV\n\
+         --hall-of-fame-dir writes each run's hall of fame of every species\n\
+         --genome-dir writes each run's best genome of every species\n\
+         --import-genome puts a previously exported genome into every run's first generation\n\
+         --evolve-eyes lets FOV angle and range of every species evolve, at a cost of energy and speed\n\
+         --evolve-bodies lets size, top speed and agility of every species evolve, at a cost of energy"
     );
 }
 
@@ -75,6 +99,36 @@ fn parse_args() -> Config {
                     process::exit(2);
                 }
             },
+            "--pedigree-dir" => match args.next() {
+                Some(path) if !path.is_empty() => cfg.pedigree_dir = Some(path),
+                _ => {
+                    eprintln!("Invalid value for --pedigree-dir. Expected a non-empty path.");
+                    process::exit(2);
+                }
+            },
+            "--hall-of-fame-dir" => match args.next() {
+                Some(path) if !path.is_empty() => cfg.hall_of_fame_dir = Some(path),
+                _ => {
+                    eprintln!("Invalid value for --hall-of-fame-dir. Expected a non-empty path.");
+                    process::exit(2);
+                }
+            },
+            "--genome-dir" => match args.next() {
+                Some(path) if !path.is_empty() => cfg.genome_dir = Some(path),
+                _ => {
+                    eprintln!("Invalid value for --genome-dir. Expected a non-empty path.");
+                    process::exit(2);
+                }
+            },
+            "--import-genome" => match args.next() {
+                Some(path) if !path.is_empty() => cfg.import_genomes.push(path),
+                _ => {
+                    eprintln!("Invalid value for --import-genome. Expected a non-empty path.");
+                    process::exit(2);
+                }
+            },
+            "--evolve-eyes" => cfg.evolve_eyes = true,
+            "--evolve-bodies" => cfg.evolve_bodies = true,
             "--help" | "-h" => {
                 print_help(&program);
                 process::exit(0);
@@ -90,49 +144,185 @@ fn parse_args() -> Config {
     cfg
 }
 
+fn write_pedigree(
+    dir: &Path,
+    run: u32,
+    species: &str,
+    genealogy: &Genealogy,
+) -> std::io::Result<()> {
+    let mut dot = BufWriter::new(File::create(dir.join(format!("run{run}_{species}.dot")))?);
+    genealogy.write_dot(&mut dot)?;
+    dot.flush()?;
+
+    let mut nodes = BufWriter::new(File::create(
+        dir.join(format!("run{run}_{species}_nodes.csv")),
+    )?);
+    genealogy.write_nodes_csv(&mut nodes)?;
+    nodes.flush()?;
+
+    let mut edges = BufWriter::new(File::create(
+        dir.join(format!("run{run}_{species}_edges.csv")),
+    )?);
+    genealogy.write_edges_csv(&mut edges)?;
+    edges.flush()
+}
+
+fn write_hall_of_fame(
+    dir: &Path,
+    run: u32,
+    species: &str,
+    hall_of_fame: &HallOfFame,
+) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(dir.join(format!("run{run}_{species}.hof")))?);
+    hall_of_fame.save(&mut out)?;
+    out.flush()
+}
+
+fn write_genome(
+    dir: &Path,
+    run: u32,
+    species: &str,
+    genome: Option<Genome>,
+) -> std::io::Result<()> {
+    let Some(genome) = genome else {
+        return Ok(());
+    };
+
+    let mut out = BufWriter::new(File::create(
+        dir.join(format!("run{run}_{species}.genome")),
+    )?);
+    genome.write(&mut out).map_err(std::io::Error::other)?;
+    out.flush()
+}
+
+fn read_genomes(paths: &[String]) -> Vec<Genome> {
+    paths
+        .iter()
+        .map(|path| {
+            let genome = File::open(path)
+                .map_err(GenomeError::from)
+                .and_then(|mut file| Genome::read(&mut file));
+
+            genome.unwrap_or_else(|err| {
+                eprintln!("Failed to import genome from {path}: {err}");
+                process::exit(2);
+            })
+        })
+        .collect()
+}
+
+fn simulation_config(cfg: &Config) -> SimulationConfig {
+    let mut config = SimulationConfig::default();
+
+    for species in &mut config.species {
+        if cfg.evolve_eyes {
+            species.eye_genes = Some(EyeGenes::default());
+        }
+
+        if cfg.evolve_bodies {
+            species.body_genes = Some(BodyGenes::default());
+        }
+
+        // Otherwise energy cost of evolved eyes and bodies would never bite
+        if cfg.evolve_eyes || cfg.evolve_bodies {
+            species.metabolism.get_or_insert_with(Metabolism::default);
+        }
+    }
+
+    config
+}
+
 fn run_batch(cfg: &Config) -> std::io::Result<()> {
+    let genomes = read_genomes(&cfg.import_genomes);
+
+    for dir in [&cfg.pedigree_dir, &cfg.hall_of_fame_dir, &cfg.genome_dir]
+        .into_iter()
+        .flatten()
+    {
+        fs::create_dir_all(dir)?;
+    }
+
     let file = File::create(&cfg.out)?;
     let mut out = BufWriter::new(file);
 
     writeln!(
         out,
-        "run,generation,prey_min_fitness,prey_max_fitness,prey_avg_fitness,prey_median_fitness,prey_dead,predator_min_fitness,predator_max_fitness,predator_avg_fitness,predator_median_fitness"
+        "run,generation,prey_min_fitness,prey_max_fitness,prey_avg_fitness,prey_median_fitness,prey_dead,prey_bound_hits,predator_min_fitness,predator_max_fitness,predator_avg_fitness,predator_median_fitness,predator_dead,predator_bound_hits,prey_fov_angle,prey_fov_range_scale,prey_body_size,prey_body_speed,prey_body_agility,predator_fov_angle,predator_fov_range_scale,predator_body_size,predator_body_speed,predator_body_agility"
     )?;
 
-    let per_run_lines: Vec<String> = (0..cfg.runs)
+    let per_run_lines: Vec<std::io::Result<String>> = (0..cfg.runs)
         .into_par_iter()
         .map(|run| {
             let run_seed = cfg.seed.wrapping_add(run as u64);
             let mut rng = ChaCha8Rng::seed_from_u64(run_seed);
-            let mut sim = Simulation::random(&mut rng);
+            let mut sim = Simulation::random_with_config(&mut rng, simulation_config(cfg));
+
+            for genome in &genomes {
+                sim.import_genome(&mut rng, genome)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            }
 
             let mut lines = String::with_capacity((cfg.generations as usize) * 120);
             for _ in 0..cfg.generations {
                 let stats = sim.fast_forward(&mut rng);
+                let prey = stats
+                    .species(PREY_SPECIES)
+                    .expect("prey are simulated by default");
+                let predator = stats
+                    .species(PREDATOR_SPECIES)
+                    .expect("predators are simulated by default");
+
                 writeln!(
                     lines,
-                    "{},{},{:.6},{:.6},{:.6},{:.6},{},{:.6},{:.6},{:.6},{:.6}",
+                    "{},{},{:.6},{:.6},{:.6},{:.6},{},{},{:.6},{:.6},{:.6},{:.6},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6}",
                     run,
                     stats.generation,
-                    stats.prey_ga.min_fitness,
-                    stats.prey_ga.max_fitness,
-                    stats.prey_ga.avg_fitness,
-                    stats.prey_ga.median_fitness,
-                    stats.num_dead_prey,
-                    stats.predator_ga.min_fitness,
-                    stats.predator_ga.max_fitness,
-                    stats.predator_ga.avg_fitness,
-                    stats.predator_ga.median_fitness,
+                    prey.ga.min_fitness,
+                    prey.ga.max_fitness,
+                    prey.ga.avg_fitness,
+                    prey.ga.median_fitness,
+                    prey.num_dead,
+                    prey.ga.bound_hits,
+                    predator.ga.min_fitness,
+                    predator.ga.max_fitness,
+                    predator.ga.avg_fitness,
+                    predator.ga.median_fitness,
+                    predator.num_dead,
+                    predator.ga.bound_hits,
+                    prey.morphology.fov_angle,
+                    prey.morphology.fov_range_scale,
+                    prey.morphology.body_size,
+                    prey.morphology.body_speed,
+                    prey.morphology.body_agility,
+                    predator.morphology.fov_angle,
+                    predator.morphology.fov_range_scale,
+                    predator.morphology.body_size,
+                    predator.morphology.body_speed,
+                    predator.morphology.body_agility,
                 )
                 .expect("writing CSV row into String should not fail");
             }
 
-            lines
+            for (idx, species) in sim.config().species.iter().enumerate() {
+                if let Some(dir) = &cfg.pedigree_dir {
+                    write_pedigree(Path::new(dir), run, &species.name, sim.genealogy(idx))?;
+                }
+
+                if let Some(dir) = &cfg.hall_of_fame_dir {
+                    write_hall_of_fame(Path::new(dir), run, &species.name, sim.hall_of_fame(idx))?;
+                }
+
+                if let Some(dir) = &cfg.genome_dir {
+                    write_genome(Path::new(dir), run, &species.name, sim.best_genome(idx))?;
+                }
+            }
+
+            Ok(lines)
         })
         .collect();
 
     for lines in per_run_lines {
-        out.write_all(lines.as_bytes())?;
+        out.write_all(lines?.as_bytes())?;
     }
 
     out.flush()
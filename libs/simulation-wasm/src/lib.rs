@@ -2,6 +2,10 @@ use lib_simulation as sim;
 use rand::prelude::*;
 use wasm_bindgen::prelude::*;
 
+// Where prey and predators are within `sim::SimulationConfig::species`
+const PREY_SPECIES_IDX: usize = 0;
+const PREDATOR_SPECIES_IDX: usize = 1;
+
 #[wasm_bindgen]
 pub struct Simulation {
     rng: ThreadRng,
@@ -57,11 +61,13 @@ pub struct GenerationStats {
     pub prey_avg_fitness: f32,
     pub prey_median_fitness: f32,
     pub prey_dead: u32,
+    pub prey_bound_hits: u32,
     pub predator_min_fitness: f32,
     pub predator_max_fitness: f32,
     pub predator_avg_fitness: f32,
     pub predator_median_fitness: f32,
     pub predator_dead: u32,
+    pub predator_bound_hits: u32,
 }
 
 #[wasm_bindgen]
@@ -90,6 +96,7 @@ impl Simulation {
         GenerationStats::from(self.sim.current_statistics())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn reset(
         &mut self,
         prey: u32,
@@ -104,29 +111,58 @@ impl Simulation {
         prey_speed_mul: f32,
         pred_speed_mul: f32,
     ) -> GenerationStats {
+        let prey = sim::SpeciesConfig {
+            count: prey as usize,
+            hidden_neurons: prey_n as usize,
+            fov_angle: prey_fov,
+            ..sim::SpeciesConfig::prey()
+        };
+
+        let predator = sim::SpeciesConfig {
+            count: pred as usize,
+            hidden_neurons: pred_n as usize,
+            fov_angle: pred_fov,
+            ..sim::SpeciesConfig::predator(0)
+        };
+
         let cfg = sim::SimulationConfig {
-            prey_count: prey as usize,
-            predator_count: pred as usize,
+            species: vec![
+                prey.with_photoreceptors(prey_p as usize)
+                    .with_speed_multiplier(prey_speed_mul),
+                predator
+                    .with_photoreceptors(pred_p as usize)
+                    .with_speed_multiplier(pred_speed_mul),
+            ],
             food_count: foods as usize,
-            prey_hidden_neurons: prey_n as usize,
-            predator_hidden_neurons: pred_n as usize,
-            prey_photoreceptors: prey_p as usize,
-            predator_photoreceptors: pred_p as usize,
-            prey_fov_angle: prey_fov,
-            predator_fov_angle: pred_fov,
-            prey_speed_multiplier: prey_speed_mul,
-            predator_speed_multiplier: pred_speed_mul,
+            ..Default::default()
         };
         self.sim.reset_with_config(&mut self.rng, cfg);
         GenerationStats::from(self.sim.current_statistics())
     }
 }
 
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl From<&sim::World> for World {
     fn from(world: &sim::World) -> Self {
-        let animals = world.animals().iter().map(Animal::from).collect();
-        let predators = world.predators().iter().map(Predator::from).collect();
-        let foods = world.foods().iter().map(Food::from).collect();
+        // Everyone who isn't prey gets drawn as a predator
+        let (animals, predators): (Vec<_>, Vec<_>) = world
+            .agents()
+            .iter()
+            .partition(|agent| agent.species() == PREY_SPECIES_IDX);
+
+        let animals = animals.into_iter().map(Animal::from).collect();
+        let predators = predators.into_iter().map(Predator::from).collect();
+        let foods = world
+            .foods()
+            .iter()
+            .filter(|food| food.is_available())
+            .map(Food::from)
+            .collect();
 
         Self {
             animals,
@@ -136,8 +172,8 @@ impl From<&sim::World> for World {
     }
 }
 
-impl From<&sim::Animal> for Animal {
-    fn from(animal: &sim::Animal) -> Self {
+impl From<&sim::Agent> for Animal {
+    fn from(animal: &sim::Agent) -> Self {
         let c = animal.color();
         let color = u32::from_be_bytes([c.r, c.g, c.b, c.a]);
 
@@ -160,8 +196,8 @@ impl From<&sim::Food> for Food {
     }
 }
 
-impl From<&sim::Predator> for Predator {
-    fn from(predator: &sim::Predator) -> Self {
+impl From<&sim::Agent> for Predator {
+    fn from(predator: &sim::Agent) -> Self {
         let c = predator.color();
         let color = u32::from_be_bytes([c.r, c.g, c.b, c.a]);
 
@@ -177,18 +213,23 @@ impl From<&sim::Predator> for Predator {
 
 impl From<sim::Statistics> for GenerationStats {
     fn from(stats: sim::Statistics) -> Self {
+        let prey = &stats.species[PREY_SPECIES_IDX];
+        let predator = &stats.species[PREDATOR_SPECIES_IDX];
+
         Self {
             generation: stats.generation as u32,
-            prey_min_fitness: stats.prey_ga.min_fitness,
-            prey_max_fitness: stats.prey_ga.max_fitness,
-            prey_avg_fitness: stats.prey_ga.avg_fitness,
-            prey_median_fitness: stats.prey_ga.median_fitness,
-            prey_dead: stats.num_dead_prey,
-            predator_min_fitness: stats.predator_ga.min_fitness,
-            predator_max_fitness: stats.predator_ga.max_fitness,
-            predator_avg_fitness: stats.predator_ga.avg_fitness,
-            predator_median_fitness: stats.predator_ga.median_fitness,
-            predator_dead: stats.num_dead_predators,
+            prey_min_fitness: prey.ga.min_fitness,
+            prey_max_fitness: prey.ga.max_fitness,
+            prey_avg_fitness: prey.ga.avg_fitness,
+            prey_median_fitness: prey.ga.median_fitness,
+            prey_dead: prey.num_dead,
+            prey_bound_hits: prey.ga.bound_hits as u32,
+            predator_min_fitness: predator.ga.min_fitness,
+            predator_max_fitness: predator.ga.max_fitness,
+            predator_avg_fitness: predator.ga.avg_fitness,
+            predator_median_fitness: predator.ga.median_fitness,
+            predator_dead: predator.num_dead,
+            predator_bound_hits: predator.ga.bound_hits as u32,
         }
     }
 }
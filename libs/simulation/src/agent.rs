@@ -0,0 +1,195 @@
+use crate::*;
+
+// An animal of any species, see `SpeciesConfig`
+#[derive(Debug)]
+pub struct Agent {
+    // Index into `SimulationConfig::species`
+    pub(crate) species: usize,
+    pub(crate) position: na::Point2<f32>,
+    pub(crate) rotation: na::Rotation2<f32>,
+    pub(crate) speed: f32,
+    // One per `SpeciesConfig::channels`
+    pub(crate) eyes: Vec<Eye>,
+    pub(crate) eye_shape: EyeShape,
+    pub(crate) body_shape: BodyShape,
+    pub(crate) brain: Brain,
+    // Heritable traits other than the brain, see
+    // `SpeciesConfig::trait_genes()`
+    pub(crate) genes: Vec<f32>,
+    // Species' body and mobility adjusted by `eye_shape` and `body_shape`
+    pub(crate) speed_max: f32,
+    pub(crate) rotation_accel: f32,
+    pub(crate) body_radius: f32,
+    pub(crate) reach: f32,
+    pub(crate) satiation: f32,
+    pub(crate) color: Rgba,
+    pub(crate) lineage: Option<ga::Lineage>,
+    // Built from a genome by `Simulation::import_genome()`
+    pub(crate) imported: bool,
+    pub(crate) trajectory: Trajectory,
+    pub(crate) ticks_alive: usize,
+    // Within `0.0..=1.0`, see `Metabolism`
+    pub(crate) energy: f32,
+    // See `Hunger`
+    pub(crate) hunger: f32,
+    // Sum of distances to the closest hunter (capped at how far it can see),
+    // over the ticks at which its species had any hunters
+    pub(crate) predator_distance: f32,
+    pub(crate) predator_distance_samples: usize,
+    pub(crate) kills: usize,
+    // Food eaten plus animals caught, see `PopulationDynamics::Resources`
+    pub(crate) meals: usize,
+    pub alive: bool,
+}
+
+impl Agent {
+    pub(crate) fn random(rng: &mut dyn RngCore, config: &SimulationConfig, species: usize) -> Self {
+        let cfg = &config.species[species];
+        let brain = Brain::random(rng, cfg.input_neurons(), cfg.hidden_neurons);
+        let genes = random_genes(rng, cfg.trait_genes());
+
+        Self::new(config, species, brain, genes, rng)
+    }
+
+    pub(crate) fn from_chromosome(
+        chromosome: ga::Chromosome,
+        config: &SimulationConfig,
+        species: usize,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let cfg = &config.species[species];
+
+        // Brain weights come first, followed by trait genes
+        let mut weights: Vec<_> = chromosome.into_iter().collect();
+        let genes = weights.split_off(Brain::weight_count(cfg.input_neurons(), cfg.hidden_neurons));
+        let brain = Brain::from_chromosome(
+            weights.into_iter().collect(),
+            cfg.input_neurons(),
+            cfg.hidden_neurons,
+        );
+
+        Self::new(config, species, brain, genes, rng)
+    }
+
+    fn new(
+        config: &SimulationConfig,
+        species: usize,
+        brain: Brain,
+        genes: Vec<f32>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let cfg = &config.species[species];
+        let eye_shape = cfg.eye_shape(&genes);
+        let body_shape = cfg.body_shape(&genes);
+        let speed_max =
+            (cfg.speed_max * body_shape.speed * (1.0 - eye_shape.speed_cost)).max(cfg.speed_min);
+
+        Self {
+            species,
+            position: rng.r#gen(),
+            rotation: rng.r#gen(),
+            speed: cfg.initial_speed.min(speed_max),
+            eyes: cfg.eyes(&eye_shape),
+            eye_shape,
+            body_shape,
+            brain,
+            genes,
+            speed_max,
+            rotation_accel: cfg.rotation_accel * body_shape.agility,
+            body_radius: cfg.body_radius * body_shape.size,
+            reach: cfg.reach * body_shape.size,
+            satiation: 0.0,
+            color: cfg.color,
+            lineage: None,
+            imported: false,
+            trajectory: Trajectory::default(),
+            ticks_alive: 0,
+            energy: 1.0,
+            hunger: 0.0,
+            predator_distance: 0.0,
+            predator_distance_samples: 0,
+            kills: 0,
+            meals: 0,
+            alive: true,
+        }
+    }
+
+    pub fn species(&self) -> usize {
+        self.species
+    }
+
+    pub fn position(&self) -> na::Point2<f32> {
+        self.position
+    }
+
+    pub fn rotation(&self) -> na::Rotation2<f32> {
+        self.rotation
+    }
+
+    pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
+        self.brain
+            .as_chromosome()
+            .into_iter()
+            .chain(self.genes.iter().copied())
+            .collect()
+    }
+
+    pub fn color(&self) -> Rgba {
+        self.color
+    }
+
+    pub fn lineage(&self) -> Option<&ga::Lineage> {
+        self.lineage.as_ref()
+    }
+
+    pub fn trajectory(&self) -> &Trajectory {
+        &self.trajectory
+    }
+
+    pub fn ticks_alive(&self) -> usize {
+        self.ticks_alive
+    }
+
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    pub fn hunger(&self) -> f32 {
+        self.hunger
+    }
+
+    pub fn eye_shape(&self) -> &EyeShape {
+        &self.eye_shape
+    }
+
+    pub fn body_shape(&self) -> &BodyShape {
+        &self.body_shape
+    }
+
+    // Energy spent each tick on top of `Metabolism`, for having the eyes
+    // and body it has
+    pub(crate) fn upkeep(&self) -> f32 {
+        self.eye_shape.energy_cost + self.body_shape.energy_cost
+    }
+
+    pub fn kills(&self) -> usize {
+        self.kills
+    }
+
+    // `lifespan` is how long it could have lived at most, see
+    // `EvolutionMode::lifespan()`
+    pub fn performance(&self, lifespan: usize) -> Performance {
+        Performance {
+            satiation: self.satiation,
+            survival: survival(self.ticks_alive, lifespan),
+            predator_distance: self.predator_distance
+                / self.predator_distance_samples.max(1) as f32,
+            kills: self.kills,
+        }
+    }
+
+    pub(crate) fn die(&mut self) {
+        self.alive = false;
+        self.speed = 0.0;
+    }
+}
@@ -0,0 +1,65 @@
+use crate::*;
+
+pub struct AgentIndividual {
+    pub(crate) fitness: f32,
+    chromosome: ga::Chromosome,
+    lineage: Option<ga::Lineage>,
+}
+
+impl AgentIndividual {
+    // `lifespan` is how long agents get to live at most, see
+    // `EvolutionMode::lifespan()`
+    pub fn from_agent(agent: &Agent, fitness: &dyn FitnessFunction, lifespan: usize) -> Self {
+        Self {
+            fitness: fitness.fitness(&agent.performance(lifespan)),
+            chromosome: agent.as_chromosome(),
+            lineage: agent.lineage.clone(),
+        }
+    }
+
+    // Makes sure this individual's offspring respect given bounds
+    pub(crate) fn with_bounds(mut self, bounds: Option<&Arc<ga::Bounds>>) -> Self {
+        if let Some(bounds) = bounds {
+            self.chromosome = self.chromosome.with_bounds(bounds.clone());
+        }
+
+        self
+    }
+
+    pub fn into_agent(
+        self,
+        config: &SimulationConfig,
+        species: usize,
+        rng: &mut dyn RngCore,
+    ) -> Agent {
+        let mut agent = Agent::from_chromosome(self.chromosome, config, species, rng);
+        agent.lineage = self.lineage;
+        agent
+    }
+}
+
+impl ga::Individual for AgentIndividual {
+    fn create(chromosome: ga::Chromosome) -> Self {
+        Self {
+            fitness: 0.0,
+            chromosome,
+            lineage: None,
+        }
+    }
+
+    fn chromosome(&self) -> &ga::Chromosome {
+        &self.chromosome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn lineage(&self) -> Option<&ga::Lineage> {
+        self.lineage.as_ref()
+    }
+
+    fn set_lineage(&mut self, lineage: ga::Lineage) {
+        self.lineage = Some(lineage);
+    }
+}
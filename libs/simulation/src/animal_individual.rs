@@ -1,50 +0,0 @@
-use crate::*;
-
-pub struct AnimalIndividual {
-    fitness: f32,
-    chromosome: ga::Chromosome,
-}
-
-impl AnimalIndividual {
-    pub fn from_animal(animal: &Animal) -> Self {
-        Self {
-            fitness: 1.0 + animal.satiation as f32,
-            chromosome: animal.as_chromosome(),
-        }
-    }
-
-    pub fn into_animal(
-        self,
-        eye_cells: usize,
-        fov_angle: f32,
-        hidden_neurons: usize,
-        speed_multiplier: f32,
-        rng: &mut dyn RngCore,
-    ) -> Animal {
-        Animal::from_chromosome(
-            self.chromosome,
-            eye_cells,
-            fov_angle,
-            hidden_neurons,
-            speed_multiplier,
-            rng,
-        )
-    }
-}
-
-impl ga::Individual for AnimalIndividual {
-    fn create(chromosome: ga::Chromosome) -> Self {
-        Self {
-            fitness: 0.0,
-            chromosome,
-        }
-    }
-
-    fn chromosome(&self) -> &ga::Chromosome {
-        &self.chromosome
-    }
-
-    fn fitness(&self) -> f32 {
-        self.fitness
-    }
-}
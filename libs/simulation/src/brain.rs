@@ -34,6 +34,22 @@ impl Brain {
         rgba_from_bytes(&bytes)
     }
 
+    pub(crate) fn layer_sizes(input_neurons: usize, hidden_neurons: usize) -> Vec<usize> {
+        Self::topology(input_neurons, hidden_neurons)
+            .iter()
+            .map(|layer| layer.neurons)
+            .collect()
+    }
+
+    // How many genes a chromosome encoding such brain consists of - each
+    // neuron has a bias plus one weight per neuron of the previous layer
+    pub(crate) fn weight_count(input_neurons: usize, hidden_neurons: usize) -> usize {
+        Self::layer_sizes(input_neurons, hidden_neurons)
+            .windows(2)
+            .map(|layers| (layers[0] + 1) * layers[1])
+            .sum()
+    }
+
     fn topology(input_neurons: usize, hidden_neurons: usize) -> [nn::LayerTopology; 3] {
         [
             nn::LayerTopology { neurons: input_neurons },
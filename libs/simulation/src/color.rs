@@ -18,7 +18,7 @@ pub fn rgba_from_bytes(bytes: &[u8]) -> Rgba {
     }
 
     // Smooth hue from circular projection
-    let w = 0.61803398875_f32; // golden-ratio-ish frequency
+    let w = 0.618_034_f32; // golden-ratio-ish frequency
     let (mut sx, mut sy) = (0.0_f32, 0.0_f32);
     let (mut mn, mut mx, mut sum) = (u8::MAX, u8::MIN, 0u32);
 
@@ -2,7 +2,7 @@ use crate::*;
 use std::f32::consts::*;
 
 // 0 to 1 inclusive
-const FOV_RANGE: f32 = 0.25;
+pub(crate) const FOV_RANGE: f32 = 0.25;
 
 // 0 to 2 * PI
 const FOV_ANGLE: f32 = PI + FRAC_PI_4;
@@ -11,6 +11,15 @@ const FOV_ANGLE: f32 = PI + FRAC_PI_4;
 // 3-11 is okay, any more than 20 will significantly slow the evolution
 const CELLS: usize = 9;
 
+// Something an eye can see - a circle of given radius and kind (e.g. the
+// food type or species), see `Eye::process_nearest()`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sighting {
+    pub position: na::Point2<f32>,
+    pub radius: f32,
+    pub kind: usize,
+}
+
 #[derive(Debug)]
 pub struct Eye {
     fov_range: f32,
@@ -22,7 +31,7 @@ impl Eye {
     // FOV_RANGE, FOV_ANGLE & CELLS are the values we'll use during
     // simulation - but being able to create an arbitrary eye will
     // come handy during the testing:
-    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+    pub(crate) fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
         assert!(fov_range > 0.0);
         assert!(fov_angle > 0.0);
         assert!(cells > 0);
@@ -38,6 +47,10 @@ impl Eye {
         self.cells
     }
 
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
     pub fn with_cells(cells: usize) -> Self {
         Self::new(FOV_RANGE, FOV_ANGLE, cells)
     }
@@ -52,7 +65,10 @@ impl Eye {
         rotation: na::Rotation2<f32>,
         foods: &[Food],
     ) -> Vec<f32> {
-        let food_positions = foods.iter().map(|food| food.position);
+        let food_positions = foods
+            .iter()
+            .filter(|food| food.is_available())
+            .map(|food| food.position);
         self.process_vision_positions(position, rotation, food_positions)
     }
 
@@ -63,15 +79,20 @@ impl Eye {
         points: impl IntoIterator<Item = na::Point2<f32>>,
     ) -> Vec<f32> {
         let mut cells = vec![0.0; self.cells];
+        let rotation = rotation.angle();
+
         for point in points {
             let vec = point - position;
-            let dist = vec.norm();
-            if dist >= self.fov_range {
+            let dist = vec.norm_squared();
+            if dist >= self.fov_range * self.fov_range {
                 continue;
             }
+            let dist = dist.sqrt();
 
-            let angle = na::Rotation2::rotation_between(&na::Vector2::y(), &vec).angle();
-            let angle = angle - rotation.angle();
+            // Same as `Rotation2::rotation_between(&Vector2::y(), &vec)`,
+            // just cheaper - this loop is the hottest part of the simulation
+            let angle = f32::atan2(-vec.x, vec.y);
+            let angle = angle - rotation;
             let angle = na::wrap(angle, -PI, PI);
             if angle < -self.fov_angle / 2.0 || angle > self.fov_angle / 2.0 {
                 continue;
@@ -87,6 +108,132 @@ impl Eye {
         }
         cells
     }
+
+    // Reports how close the nearest object in each cell is - `1.0` right in
+    // front of the eye, fading to `0.0` at the edge of its range.
+    //
+    // With `occlusion`, objects hidden behind others don't count; with
+    // `kinds`, each cell is followed by an extra input telling what kind
+    // of object it sees, from `1.0 / kinds` to `1.0` (or `0.0` when the cell
+    // is empty).
+    pub fn process_nearest(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        objects: &[Sighting],
+        occlusion: bool,
+        kinds: Option<usize>,
+    ) -> Vec<f32> {
+        // Closeness and kind of the nearest object in each cell
+        let mut nearest: Vec<Option<(f32, usize)>> = vec![None; self.cells];
+
+        for (idx, object) in objects.iter().enumerate() {
+            let vec = object.position - position;
+            let dist = vec.norm();
+
+            if dist >= self.fov_range {
+                continue;
+            }
+
+            let Some(cell) = self.cell(vec, rotation) else {
+                continue;
+            };
+
+            let closeness = (self.fov_range - dist) / self.fov_range;
+
+            if nearest[cell].is_some_and(|(other, _)| other >= closeness) {
+                continue;
+            }
+
+            if occlusion && is_occluded(position, object.position, idx, objects) {
+                continue;
+            }
+
+            nearest[cell] = Some((closeness, object.kind));
+        }
+
+        nearest
+            .into_iter()
+            .flat_map(|cell| {
+                let closeness = cell.map_or(0.0, |(closeness, _)| closeness);
+
+                let kind = kinds.map(|kinds| {
+                    cell.map_or(0.0, |(_, kind)| (kind + 1) as f32 / kinds.max(1) as f32)
+                });
+
+                std::iter::once(closeness).chain(kind)
+            })
+            .collect()
+    }
+
+    // Returns the cell seeing given vector (relative to the eye), if any
+    fn cell(&self, vec: na::Vector2<f32>, rotation: na::Rotation2<f32>) -> Option<usize> {
+        let angle = f32::atan2(-vec.x, vec.y) - rotation.angle();
+        let angle = na::wrap(angle, -PI, PI);
+
+        if angle < -self.fov_angle / 2.0 || angle > self.fov_angle / 2.0 {
+            return None;
+        }
+
+        let cell = (angle + self.fov_angle / 2.0) / self.fov_angle * self.cells as f32;
+
+        Some((cell as usize).min(self.cells - 1))
+    }
+
+    // Casts a ray through the middle of each cell and reports how close
+    // the nearest obstacle it hits is - `1.0` right in front of the eye,
+    // fading to `0.0` at the edge of its range
+    pub fn process_obstacles(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        obstacles: &[Obstacle],
+    ) -> Vec<f32> {
+        (0..self.cells)
+            .map(|cell| {
+                let angle =
+                    (cell as f32 + 0.5) / self.cells as f32 * self.fov_angle - self.fov_angle / 2.0;
+                let direction = na::Rotation2::new(rotation.angle() + angle) * na::Vector2::y();
+
+                obstacles
+                    .iter()
+                    .filter_map(|obstacle| obstacle.raycast(position, direction, self.fov_range))
+                    .min_by(|a, b| a.total_cmp(b))
+                    .map_or(0.0, |dist| (self.fov_range - dist) / self.fov_range)
+            })
+            .collect()
+    }
+}
+
+// Whether the line of sight from `position` to `objects[target]` crosses
+// any other object first
+fn is_occluded(
+    position: na::Point2<f32>,
+    target: na::Point2<f32>,
+    target_idx: usize,
+    objects: &[Sighting],
+) -> bool {
+    let vec = target - position;
+    let dist = vec.norm();
+
+    if dist <= 0.0 {
+        return false;
+    }
+
+    let direction = vec / dist;
+
+    objects.iter().enumerate().any(|(idx, other)| {
+        // Whatever the eye itself is inside of (e.g. a body it bumped into)
+        // doesn't block its view
+        idx != target_idx
+            && na::distance(&position, &other.position) > other.radius
+            && Obstacle::Circle {
+                center: other.position,
+                radius: other.radius,
+            }
+            .raycast(position, direction, dist)
+            .is_some_and(|hit| hit < dist)
+    })
 }
 
 impl Default for Eye {
@@ -151,9 +298,7 @@ mod tests {
     }
 
     fn food(x: f32, y: f32) -> Food {
-        Food {
-            position: na::Point2::new(x, y),
-        }
+        Food::new(na::Point2::new(x, y), 0, &FoodType::default())
     }
 
     #[test_case(1.0, "      +      ")] // Food is inside the FOV
@@ -269,4 +414,94 @@ mod tests {
         }
         .run()
     }
+
+    #[test]
+    fn obstacles() {
+        let eye = Eye::new(0.5, FRAC_PI_2, 3);
+
+        // Looking up, with a wall right ahead and nothing on the sides
+        let vision = eye.process_obstacles(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &[Obstacle::Segment {
+                from: na::Point2::new(0.45, 0.75),
+                to: na::Point2::new(0.55, 0.75),
+            }],
+        );
+
+        assert_eq!(vision[0], 0.0);
+        assert!((vision[1] - 0.5).abs() < 1e-5, "{vision:?}");
+        assert_eq!(vision[2], 0.0);
+    }
+
+    fn sighting(x: f32, y: f32, kind: usize) -> Sighting {
+        Sighting {
+            position: na::Point2::new(x, y),
+            radius: 0.02,
+            kind,
+        }
+    }
+
+    #[test]
+    fn nearest() {
+        let eye = Eye::new(0.5, FRAC_PI_2, 3);
+        let position = na::Point2::new(0.5, 0.5);
+        let rotation = na::Rotation2::new(0.0);
+
+        // Three foods straight ahead, at different distances
+        let objects = [
+            sighting(0.5, 0.9, 0),
+            sighting(0.5, 0.75, 1),
+            sighting(0.5, 0.8, 0),
+        ];
+
+        let summed = eye.process_vision_positions(
+            position,
+            rotation,
+            objects.iter().map(|object| object.position),
+        );
+
+        let nearest = eye.process_nearest(position, rotation, &objects, false, None);
+
+        assert!(summed[1] > 1.0, "{summed:?}");
+        assert_eq!(nearest.len(), 3);
+        assert!((nearest[1] - 0.5).abs() < 1e-5, "{nearest:?}");
+        assert_eq!([nearest[0], nearest[2]], [0.0, 0.0]);
+
+        let typed = eye.process_nearest(position, rotation, &objects, false, Some(2));
+
+        assert_eq!(typed.len(), 6);
+        assert!((typed[2] - 0.5).abs() < 1e-5, "{typed:?}");
+        assert_eq!(typed[3], 1.0);
+        assert_eq!([typed[0], typed[1], typed[4], typed[5]], [0.0; 4]);
+    }
+
+    #[test]
+    fn occlusion() {
+        let eye = Eye::new(0.5, FRAC_PI_2, 3);
+        let position = na::Point2::new(0.5, 0.5);
+        let rotation = na::Rotation2::new(0.0);
+
+        // A big object straight ahead and a small one a bit to the left,
+        // further away - behind the big one
+        let wall = Sighting {
+            radius: 0.1,
+            ..sighting(0.5, 0.65, 0)
+        };
+        let target = sighting(0.363, 0.876, 1);
+        let objects = [target, wall];
+
+        let seen = eye.process_nearest(position, rotation, &objects, false, None);
+        let occluded = eye.process_nearest(position, rotation, &objects, true, None);
+
+        assert!((seen[1] - 0.7).abs() < 1e-5, "{seen:?}");
+        assert!((seen[2] - 0.2).abs() < 1e-3, "{seen:?}");
+        assert!((occluded[1] - 0.7).abs() < 1e-5, "{occluded:?}");
+        assert_eq!(occluded[2], 0.0);
+
+        // Whatever the eye itself is inside of doesn't count
+        let inside = eye.process_nearest(na::Point2::new(0.5, 0.6), rotation, &objects, true, None);
+
+        assert!(inside[2] > 0.0, "{inside:?}");
+    }
 }
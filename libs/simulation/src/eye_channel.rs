@@ -0,0 +1,87 @@
+use crate::*;
+
+// What an eye channel sees
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelKind {
+    Food,
+    // Animals of species this one hunts
+    Prey,
+    // Animals of species hunting this one
+    Predators,
+    // Other animals of this very species
+    SameSpecies,
+    // See `Eye::process_obstacles()`
+    Obstacles,
+}
+
+// How eye cells turn what they see into brain inputs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EyeMode {
+    // Each cell sums up how close everything in it is, so ten distant
+    // objects may look like a single close one
+    #[default]
+    Summed,
+    // Each cell reports how close the nearest object in it is, see
+    // `Eye::process_nearest()`; with `occlusion`, objects hidden behind
+    // others aren't seen and with `object_types`, each cell also tells what
+    // kind of object (food type or species) it sees
+    Nearest {
+        occlusion: bool,
+        object_types: bool,
+    },
+}
+
+// A single layer of vision, each feeding its own `cells` (or, with
+// `EyeMode::Nearest::object_types`, twice as many) inputs to the brain.
+//
+// All channels share species' `SpeciesConfig::fov_angle`, but can see as
+// far (and as precisely) as they need to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EyeChannel {
+    pub kind: ChannelKind,
+    // Multiplies everything this channel sees, e.g. so that predators look
+    // more alarming than food
+    pub gain: f32,
+    pub fov_range: f32,
+    pub cells: usize,
+}
+
+impl EyeChannel {
+    pub fn new(kind: ChannelKind) -> Self {
+        let eye = Eye::default();
+
+        Self {
+            kind,
+            gain: 1.0,
+            fov_range: eye.fov_range(),
+            cells: eye.cells(),
+        }
+    }
+
+    pub(crate) fn normalized(self) -> Self {
+        Self {
+            gain: self.gain.max(0.0),
+            fov_range: self.fov_range.clamp(0.001, 1.0),
+            cells: self.cells.max(1),
+            ..self
+        }
+    }
+
+    // Number of brain inputs this channel takes
+    pub(crate) fn inputs(&self, mode: EyeMode) -> usize {
+        let object_types = match mode {
+            EyeMode::Summed => false,
+            EyeMode::Nearest { object_types, .. } => object_types,
+        };
+
+        if object_types && self.kind != ChannelKind::Obstacles {
+            self.cells * 2
+        } else {
+            self.cells
+        }
+    }
+
+    pub(crate) fn eye(&self, fov_angle: f32) -> Eye {
+        Eye::new(self.fov_range, fov_angle, self.cells)
+    }
+}
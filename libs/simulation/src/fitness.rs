@@ -0,0 +1,186 @@
+use crate::*;
+use std::fmt;
+
+// What an animal or predator achieved during its life; dead ones keep the
+// values from the moment they died
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Performance {
+    // Nutrition of everything eaten (three per prey caught, for predators)
+    pub satiation: f32,
+    // Fraction of its lifespan it stayed alive for, within `0.0..=1.0`
+    pub survival: f32,
+    // Average distance to the closest predator, prey only
+    pub predator_distance: f32,
+    pub kills: usize,
+}
+
+// Turns performance into fitness; the result may be negative, in which
+// case the whole population's fitness gets shifted up before selection
+pub trait FitnessFunction: fmt::Debug + Send + Sync {
+    fn fitness(&self, performance: &Performance) -> f32;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Satiation;
+
+impl FitnessFunction for Satiation {
+    fn fitness(&self, performance: &Performance) -> f32 {
+        performance.satiation
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Survival;
+
+impl FitnessFunction for Survival {
+    fn fitness(&self, performance: &Performance) -> f32 {
+        performance.survival
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PredatorDistance;
+
+impl FitnessFunction for PredatorDistance {
+    fn fitness(&self, performance: &Performance) -> f32 {
+        performance.predator_distance
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Kills;
+
+impl FitnessFunction for Kills {
+    fn fitness(&self, performance: &Performance) -> f32 {
+        performance.kills as f32
+    }
+}
+
+// E.g. `WeightedSum::new().with(1.0, Satiation).with(-2.0, Kills)`
+#[derive(Debug, Default)]
+pub struct WeightedSum {
+    terms: Vec<(f32, Box<dyn FitnessFunction>)>,
+}
+
+impl WeightedSum {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, weight: f32, function: impl FitnessFunction + 'static) -> Self {
+        self.terms.push((weight, Box::new(function)));
+        self
+    }
+}
+
+impl FitnessFunction for WeightedSum {
+    fn fitness(&self, performance: &Performance) -> f32 {
+        self.terms
+            .iter()
+            .map(|(weight, function)| weight * function.fitness(performance))
+            .sum()
+    }
+}
+
+// Food eaten plus the fraction of its lifespan survived - so survivors
+// score `1.0 + satiation` and those that died early still count for the
+// time they lasted
+pub(crate) fn default_fitness_function() -> Arc<dyn FitnessFunction> {
+    Arc::new(WeightedSum::new().with(1.0, Satiation).with(1.0, Survival))
+}
+
+// Roulette wheel selection requires non-negative weights, at least one of
+// them positive
+pub(crate) fn make_selectable<'a>(fitnesses: impl IntoIterator<Item = &'a mut f32>) {
+    let mut fitnesses: Vec<_> = fitnesses.into_iter().collect();
+    let min = fitnesses.iter().fold(f32::INFINITY, |min, f| min.min(**f));
+
+    if min < 0.0 {
+        for fitness in &mut fitnesses {
+            **fitness -= min;
+        }
+    }
+
+    if fitnesses.iter().all(|fitness| **fitness <= 0.0) {
+        for fitness in &mut fitnesses {
+            **fitness = 1.0;
+        }
+    }
+}
+
+pub(crate) fn survival(ticks_alive: usize, lifespan: usize) -> f32 {
+    (ticks_alive as f32 / lifespan.max(1) as f32).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERFORMANCE: Performance = Performance {
+        satiation: 4.0,
+        survival: 0.5,
+        predator_distance: 0.2,
+        kills: 3,
+    };
+
+    #[test]
+    fn functions() {
+        assert_eq!(Satiation.fitness(&PERFORMANCE), 4.0);
+        assert_eq!(Survival.fitness(&PERFORMANCE), 0.5);
+        assert_eq!(PredatorDistance.fitness(&PERFORMANCE), 0.2);
+        assert_eq!(Kills.fitness(&PERFORMANCE), 3.0);
+        assert_eq!(default_fitness_function().fitness(&PERFORMANCE), 4.5);
+    }
+
+    #[test]
+    fn weighted_sum() {
+        let function = WeightedSum::new()
+            .with(2.0, Satiation)
+            .with(-1.0, Kills)
+            .with(10.0, PredatorDistance);
+
+        assert_eq!(function.fitness(&PERFORMANCE), 7.0);
+        assert_eq!(WeightedSum::new().fitness(&PERFORMANCE), 0.0);
+
+        let negative = WeightedSum::new().with(-1.0, Kills);
+        assert_eq!(negative.fitness(&PERFORMANCE), -3.0);
+    }
+
+    #[test]
+    fn selectable() {
+        let function = WeightedSum::new().with(1.0, Satiation).with(-1.0, Kills);
+
+        let mut fitnesses: Vec<_> = [(1.0, 3), (5.0, 1), (2.0, 2)]
+            .into_iter()
+            .map(|(satiation, kills)| {
+                function.fitness(&Performance {
+                    satiation,
+                    kills,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        assert_eq!(fitnesses, [-2.0, 4.0, 0.0]);
+
+        make_selectable(&mut fitnesses);
+        assert_eq!(fitnesses, [0.0, 6.0, 2.0]);
+
+        // Non-negative ones stay as they are
+        make_selectable(&mut fitnesses);
+        assert_eq!(fitnesses, [0.0, 6.0, 2.0]);
+
+        // Nobody can be picked out of all zeros
+        let mut fitnesses = [-1.0, -1.0];
+        make_selectable(&mut fitnesses);
+        assert_eq!(fitnesses, [1.0, 1.0]);
+    }
+
+    #[test]
+    fn survival_fraction() {
+        assert_eq!(survival(0, GENERATION_LENGTH), 0.0);
+        assert_eq!(survival(GENERATION_LENGTH / 2, GENERATION_LENGTH), 0.5);
+        assert_eq!(survival(GENERATION_LENGTH * 2, GENERATION_LENGTH), 1.0);
+        assert_eq!(survival(50, 200), 0.25);
+    }
+}
@@ -1,18 +1,237 @@
 use crate::*;
+use rand::seq::SliceRandom;
+
+// How big food looks, for the sake of `EyeMode::Nearest`'s occlusion
+pub(crate) const FOOD_RADIUS: f32 = 0.006;
 
 #[derive(Debug)]
 pub struct Food {
     pub(crate) position: na::Point2<f32>,
+    // Index into `SimulationConfig::food_types`
+    pub(crate) kind: usize,
+    pub(crate) nutrition: f32,
+    // Ticks left until eaten food grows back; `None` while it's there to
+    // be eaten
+    pub(crate) regrowth: Option<usize>,
 }
 
 impl Food {
     pub fn random(rng: &mut dyn RngCore) -> Self {
+        Self::new(rng.r#gen(), 0, &FoodType::default())
+    }
+
+    pub(crate) fn new(position: na::Point2<f32>, kind: usize, food_type: &FoodType) -> Self {
         Self {
-            position: rng.r#gen(),
+            position,
+            kind,
+            nutrition: food_type.nutrition,
+            regrowth: None,
         }
     }
 
     pub fn position(&self) -> na::Point2<f32> {
         self.position
     }
+
+    pub fn kind(&self) -> usize {
+        self.kind
+    }
+
+    pub fn nutrition(&self) -> f32 {
+        self.nutrition
+    }
+
+    // Whether the food is there, or has been eaten and is yet to grow back
+    pub fn is_available(&self) -> bool {
+        self.regrowth.is_none()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FoodType {
+    // Satiation gained from eating it (and, with metabolism, the fraction
+    // of `Metabolism::meal_energy` gained); negative makes it poisonous
+    pub nutrition: f32,
+    // How common it is compared to other types
+    pub abundance: f32,
+    // Ticks it takes to grow back once eaten
+    pub regrowth_ticks: usize,
+}
+
+impl Default for FoodType {
+    fn default() -> Self {
+        Self {
+            nutrition: 1.0,
+            abundance: 1.0,
+            regrowth_ticks: 0,
+        }
+    }
+}
+
+impl FoodType {
+    fn normalized(self) -> Self {
+        Self {
+            abundance: self.abundance.max(0.0),
+            ..self
+        }
+    }
+
+    // Makes sure there's at least one type that can grow
+    pub(crate) fn normalize_all(types: Vec<Self>) -> Vec<Self> {
+        let mut types: Vec<_> = types.into_iter().map(Self::normalized).collect();
+
+        if types.iter().all(|food_type| food_type.abundance == 0.0) {
+            types.push(Self::default());
+        }
+
+        types
+    }
+
+    // Picks index of a type, weighted by abundance
+    pub(crate) fn pick(rng: &mut dyn RngCore, types: &[Self]) -> usize {
+        let indices: Vec<_> = (0..types.len()).collect();
+
+        indices
+            .choose_weighted(rng, |idx| types[*idx].abundance)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+// Where food grows
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FoodSpawning {
+    // Anywhere, with equal probability
+    #[default]
+    Uniform,
+    // Within given number of circular patches, scattered randomly when the
+    // world is created
+    Patches {
+        count: usize,
+        radius: f32,
+    },
+}
+
+impl FoodSpawning {
+    pub(crate) fn normalized(self) -> Self {
+        match self {
+            Self::Uniform => Self::Uniform,
+            Self::Patches { count, radius } => Self::Patches {
+                count: count.max(1),
+                radius: radius.clamp(0.001, 1.0),
+            },
+        }
+    }
+
+    pub(crate) fn patches(
+        &self,
+        rng: &mut dyn RngCore,
+        obstacles: &[Obstacle],
+        topology: Topology,
+    ) -> Vec<na::Point2<f32>> {
+        match *self {
+            Self::Uniform => Vec::new(),
+            Self::Patches { count, .. } => (0..count)
+                .map(|_| random_free_position(rng, obstacles, topology))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn position(
+        &self,
+        rng: &mut dyn RngCore,
+        patches: &[na::Point2<f32>],
+        obstacles: &[Obstacle],
+        topology: Topology,
+    ) -> na::Point2<f32> {
+        let Self::Patches { radius, .. } = *self else {
+            return random_free_position(rng, obstacles, topology);
+        };
+
+        let Some(&center) = patches.choose(rng) else {
+            return random_free_position(rng, obstacles, topology);
+        };
+
+        for _ in 0..PLACEMENT_ATTEMPTS {
+            let offset = na::Rotation2::new(rng.gen_range(0.0..TAU))
+                * na::Vector2::new(0.0, radius * rng.r#gen::<f32>().sqrt());
+
+            let mut position = center + offset;
+
+            if topology == Topology::Torus {
+                topology.confine(&mut position, &mut na::Rotation2::identity());
+            }
+
+            if is_free(obstacles, topology, position) {
+                return position;
+            }
+        }
+
+        // The patch must be covered by obstacles
+        random_free_position(rng, obstacles, topology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn grows_within_patches() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let spawning = FoodSpawning::Patches {
+            count: 2,
+            radius: 0.1,
+        };
+
+        // Right in the corner, so that the patch wraps around the edges
+        let patches = [na::Point2::new(0.0, 0.0), na::Point2::new(0.5, 0.5)];
+
+        for _ in 0..100 {
+            let position = spawning.position(&mut rng, &patches, &[], Topology::Torus);
+
+            assert!(Topology::Torus.contains(position));
+            assert!(
+                patches
+                    .iter()
+                    .any(|patch| Topology::Torus.distance(*patch, position) <= 0.1 + 1e-5),
+                "{position:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn avoids_patches_covered_by_obstacles() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let spawning = FoodSpawning::Patches {
+            count: 1,
+            radius: 0.1,
+        };
+
+        let patches = [na::Point2::new(0.5, 0.5)];
+        let obstacles = [Obstacle::Circle {
+            center: na::Point2::new(0.5, 0.5),
+            radius: 0.2,
+        }];
+
+        let position = spawning.position(&mut rng, &patches, &obstacles, Topology::Torus);
+        assert!(is_free(&obstacles, Topology::Torus, position));
+    }
+
+    #[test]
+    fn picks_types_by_abundance() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let types = [
+            FoodType {
+                abundance: 0.0,
+                ..Default::default()
+            },
+            FoodType::default(),
+        ];
+
+        assert!((0..100).all(|_| FoodType::pick(&mut rng, &types) == 1));
+    }
 }
@@ -0,0 +1,75 @@
+// How long animals last without catching anyone.
+//
+// Hunger grows by `rate` every tick; each catch takes away `relief` of it
+// and once it exceeds `limit`, the animal starves to death.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hunger {
+    pub rate: f32,
+    // Fraction of hunger (`0.0..=1.0`) taken away by each catch
+    pub relief: f32,
+    pub limit: f32,
+}
+
+impl Default for Hunger {
+    fn default() -> Self {
+        Self {
+            rate: 0.001,
+            relief: 0.75,
+            limit: 1.0,
+        }
+    }
+}
+
+impl Hunger {
+    pub(crate) fn normalized(self) -> Self {
+        Self {
+            rate: self.rate.max(0.0),
+            relief: self.relief.clamp(0.0, 1.0),
+            limit: self.limit.max(0.0),
+        }
+    }
+
+    // Returns hunger after one more tick
+    pub(crate) fn grow(&self, hunger: f32) -> f32 {
+        hunger + self.rate
+    }
+
+    // Returns hunger after a catch
+    pub(crate) fn relieve(&self, hunger: f32) -> f32 {
+        hunger * (1.0 - self.relief)
+    }
+
+    // Returns how close to starving (`0.0..=1.0`) given hunger is
+    pub(crate) fn fraction(&self, hunger: f32) -> f32 {
+        (hunger / self.limit.max(f32::EPSILON)).min(1.0)
+    }
+
+    pub(crate) fn is_starving(&self, hunger: f32) -> bool {
+        hunger > self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hunger() {
+        let hunger = Hunger {
+            rate: 0.25,
+            relief: 0.5,
+            limit: 1.0,
+        };
+
+        let ticks: Vec<_> = std::iter::successors(Some(0.0), |h| Some(hunger.grow(*h)))
+            .take(6)
+            .collect();
+
+        assert_eq!(ticks, [0.0, 0.25, 0.5, 0.75, 1.0, 1.25]);
+        assert!(!hunger.is_starving(1.0));
+        assert!(hunger.is_starving(1.25));
+        assert_eq!(hunger.relieve(1.0), 0.5);
+        assert_eq!(hunger.fraction(0.75), 0.75);
+        assert_eq!(hunger.fraction(1.25), 1.0);
+    }
+}
@@ -1,111 +1,308 @@
-mod animal;
-mod animal_individual;
+mod agent;
+mod agent_individual;
 mod brain;
 mod color;
 mod eye;
+mod eye_channel;
+mod fitness;
 mod food;
-mod predator;
-mod predator_individual;
+mod hunger;
+mod metabolism;
+mod morphology;
+mod obstacle;
+mod senses;
+mod spatial_grid;
+mod species;
 mod statistics;
+mod topology;
+mod trajectory;
 mod world;
 
 pub use self::{
-    animal::*, animal_individual::*, brain::*, color::*, eye::*, food::*, predator::*,
-    predator_individual::*, statistics::*, world::*,
+    agent::*, agent_individual::*, brain::*, color::*, eye::*, eye_channel::*, fitness::*, food::*,
+    hunger::*, metabolism::*, morphology::*, obstacle::*, senses::*, spatial_grid::*, species::*,
+    statistics::*, topology::*, trajectory::*, world::*,
 };
 
 use lib_genetic_algorithm as ga;
 use lib_neural_network as nn;
 use nalgebra as na;
 use rand::{Rng, RngCore};
+use std::sync::Arc;
 
 // FRAC_PI_2 = PI / 2.0; a convenient shortcut
 use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU};
 
-const PREY_SPEED_MIN: f32 = 0.0007;
-const PREY_SPEED_MAX: f32 = 0.0035;
-const PREY_SPEED_ACCEL: f32 = 0.2;
-const PREY_ROTATION_ACCEL: f32 = FRAC_PI_2 / 3.0;
-
-const PREDATOR_SPEED_MIN: f32 = 0.0006;
-const PREDATOR_SPEED_MAX: f32 = 0.0032;
-const PREDATOR_SPEED_ACCEL: f32 = 0.25;
-const PREDATOR_ROTATION_ACCEL: f32 = FRAC_PI_2 / 2.5;
-const PREY_PREDATOR_VISION_GAIN: f32 = 2.5;
-
 const GENERATION_LENGTH: usize = 2500;
-const DEFAULT_PREY_NEURONS: usize = 9;
-const DEFAULT_PREDATOR_NEURONS: usize = 9;
-const DEFAULT_PREY_PHOTORECEPTORS: usize = 9;
-const DEFAULT_PREDATOR_PHOTORECEPTORS: usize = 9;
-const DEFAULT_SPEED_MULTIPLIER: f32 = 1.0;
+const DEFAULT_NEURONS: usize = 9;
 const DEFAULT_FOV_ANGLE: f32 = PI + FRAC_PI_4;
+const DEFAULT_HALL_OF_FAME_SIZE: usize = 10;
+const DEFAULT_NOVELTY_NEIGHBOURS: usize = 15;
+const DEFAULT_NOVELTY_ARCHIVE_SIZE: usize = 200;
+
+pub const PREY_SPECIES: &str = "prey";
+pub const PREDATOR_SPECIES: &str = "predator";
+
+type GeneticAlgorithm =
+    ga::GeneticAlgorithm<ga::RouletteWheelSelection, ga::UniformCrossover, ga::GaussianMutation>;
+
+// How many agents of a species each new generation gets
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PopulationDynamics {
+    // Always `SpeciesConfig::count`
+    #[default]
+    Fixed,
+    // Survivors breed `offspring_per_meal` offspring for each meal (food
+    // eaten or animal caught) they had - so populations grow and shrink
+    // with the resources available to them, within
+    // `1..=SpeciesConfig::max_count`
+    Resources {
+        offspring_per_meal: f32,
+    },
+}
+
+impl PopulationDynamics {
+    fn normalized(self) -> Self {
+        match self {
+            Self::Fixed => Self::Fixed,
+            Self::Resources { offspring_per_meal } => Self::Resources {
+                offspring_per_meal: offspring_per_meal.max(0.0),
+            },
+        }
+    }
+}
+
+// How (and when) new agents come to be
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EvolutionMode {
+    // Every `GENERATION_LENGTH` ticks the whole world gets replaced by the
+    // offspring of its inhabitants
+    #[default]
+    Generational,
+    // Agents reproduce on their own, whenever their energy reaches
+    // `reproduction_energy` - the child is a mutated copy of its parent (or
+    // a crossover with the closest mate of the same species within
+    // `mate_radius`, if there's any) and gets half of its parent's energy.
+    //
+    // Nobody lives longer than `max_age` ticks and populations are capped
+    // at `SpeciesConfig::max_count`; a species that dies out gets replaced
+    // by `SpeciesConfig::count` random newcomers.
+    //
+    // Statistics are sampled every `sample_interval` ticks.
+    Continuous {
+        reproduction_energy: f32,
+        mate_radius: f32,
+        max_age: usize,
+        sample_interval: usize,
+    },
+}
+
+impl EvolutionMode {
+    fn normalized(self) -> Self {
+        match self {
+            Self::Generational => Self::Generational,
+            Self::Continuous {
+                reproduction_energy,
+                mate_radius,
+                max_age,
+                sample_interval,
+            } => Self::Continuous {
+                reproduction_energy: reproduction_energy.clamp(0.01, 1.0),
+                mate_radius: mate_radius.max(0.0),
+                max_age: max_age.max(1),
+                sample_interval: sample_interval.max(1),
+            },
+        }
+    }
+
+    // How many ticks agents get to live at most
+    pub fn lifespan(&self) -> usize {
+        match self {
+            Self::Generational => GENERATION_LENGTH,
+            Self::Continuous { max_age, .. } => *max_age,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct SimulationConfig {
-    pub prey_count: usize,
-    pub predator_count: usize,
+    // Everyone inhabiting the world and who eats whom, see `SpeciesConfig`;
+    // empty means prey and predators
+    pub species: Vec<SpeciesConfig>,
     pub food_count: usize,
-    pub prey_hidden_neurons: usize,
-    pub predator_hidden_neurons: usize,
-    pub prey_photoreceptors: usize,
-    pub predator_photoreceptors: usize,
-    pub prey_fov_angle: f32,
-    pub predator_fov_angle: f32,
-    pub prey_speed_multiplier: f32,
-    pub predator_speed_multiplier: f32,
+    pub hall_of_fame_size: usize,
+    // Genotypes closer than this are treated as duplicates in the archive
+    pub hall_of_fame_min_distance: f32,
+    // How many offspring per generation get replaced by archived genomes
+    pub hall_of_fame_reinject: usize,
+    // Fraction of each new generation replaced by immigrants, see
+    // `ga::Immigrants`; zero disables them
+    pub immigrant_fraction: f32,
+    // When set, immigrants only arrive while genetic diversity stays below
+    // this threshold
+    pub immigrant_diversity_threshold: Option<f32>,
+    // Whether immigrants are copies of hall-of-fame genomes rather than
+    // random ones
+    pub immigrants_from_hall_of_fame: bool,
+    pub novelty_neighbours: usize,
+    pub novelty_archive_size: usize,
+    // Largest absolute value brain weights can evolve to; `None` leaves
+    // them unbounded
+    pub weight_bound: Option<f32>,
+    pub weight_bound_policy: ga::BoundPolicy,
+    pub evolution_mode: EvolutionMode,
+    pub topology: Topology,
+    pub obstacles: Vec<Obstacle>,
+    pub obstacle_response: ObstacleResponse,
+    // Kinds of food growing in the world, see `FoodType`
+    pub food_types: Vec<FoodType>,
+    pub food_spawning: FoodSpawning,
+    // How many times eaten food may grow back per generation (or per
+    // sample, in `EvolutionMode::Continuous`); `None` means no limit
+    pub food_budget: Option<usize>,
 }
 
 impl Default for SimulationConfig {
     fn default() -> Self {
         Self {
-            prey_count: PREY_COUNT,
-            predator_count: PREDATOR_COUNT,
+            species: default_species(),
             food_count: FOOD_COUNT,
-            prey_hidden_neurons: DEFAULT_PREY_NEURONS,
-            predator_hidden_neurons: DEFAULT_PREDATOR_NEURONS,
-            prey_photoreceptors: DEFAULT_PREY_PHOTORECEPTORS,
-            predator_photoreceptors: DEFAULT_PREDATOR_PHOTORECEPTORS,
-            prey_fov_angle: DEFAULT_FOV_ANGLE,
-            predator_fov_angle: DEFAULT_FOV_ANGLE,
-            prey_speed_multiplier: DEFAULT_SPEED_MULTIPLIER,
-            predator_speed_multiplier: DEFAULT_SPEED_MULTIPLIER,
+            hall_of_fame_size: DEFAULT_HALL_OF_FAME_SIZE,
+            hall_of_fame_min_distance: 0.0,
+            hall_of_fame_reinject: 0,
+            immigrant_fraction: 0.0,
+            immigrant_diversity_threshold: None,
+            immigrants_from_hall_of_fame: false,
+            novelty_neighbours: DEFAULT_NOVELTY_NEIGHBOURS,
+            novelty_archive_size: DEFAULT_NOVELTY_ARCHIVE_SIZE,
+            weight_bound: None,
+            weight_bound_policy: ga::BoundPolicy::Clamp,
+            evolution_mode: EvolutionMode::Generational,
+            topology: Topology::Torus,
+            obstacles: Vec::new(),
+            obstacle_response: ObstacleResponse::Slide,
+            food_types: vec![FoodType::default()],
+            food_spawning: FoodSpawning::Uniform,
+            food_budget: None,
         }
     }
 }
 
 impl SimulationConfig {
     fn normalized(mut self) -> Self {
-        self.prey_count = self.prey_count.max(1);
-        self.predator_count = self.predator_count.max(1);
+        if self.species.is_empty() {
+            self.species = default_species();
+        }
+
+        let num_species = self.species.len();
+
+        self.species = self
+            .species
+            .into_iter()
+            .map(|species| species.normalized(num_species))
+            .collect();
+
         self.food_count = self.food_count.max(1);
-        self.prey_hidden_neurons = self.prey_hidden_neurons.max(1);
-        self.predator_hidden_neurons = self.predator_hidden_neurons.max(1);
-        self.prey_photoreceptors = self.prey_photoreceptors.max(1);
-        self.predator_photoreceptors = self.predator_photoreceptors.max(1);
-        self.prey_fov_angle = self.prey_fov_angle.clamp(0.01, TAU);
-        self.predator_fov_angle = self.predator_fov_angle.clamp(0.01, TAU);
-        self.prey_speed_multiplier = self.prey_speed_multiplier.max(0.01);
-        self.predator_speed_multiplier = self.predator_speed_multiplier.max(0.01);
+        self.hall_of_fame_min_distance = self.hall_of_fame_min_distance.max(0.0);
+        self.hall_of_fame_reinject = self.hall_of_fame_reinject.min(self.hall_of_fame_size);
+        self.immigrant_fraction = self.immigrant_fraction.clamp(0.0, 1.0);
+        self.novelty_neighbours = self.novelty_neighbours.max(1);
+        self.weight_bound = self.weight_bound.map(f32::abs);
+        self.evolution_mode = self.evolution_mode.normalized();
+        self.obstacles = self
+            .obstacles
+            .into_iter()
+            .map(Obstacle::normalized)
+            .collect();
+        self.food_types = FoodType::normalize_all(self.food_types);
+        self.food_spawning = self.food_spawning.normalized();
+
+        // Without metabolism there'd be no way to earn (or lose) energy
+        if let EvolutionMode::Continuous { .. } = self.evolution_mode {
+            for species in &mut self.species {
+                species.metabolism.get_or_insert_with(Metabolism::default);
+            }
+        }
         self
     }
+
+    // Species hunting given one
+    fn hunters(&self, species: usize) -> impl Iterator<Item = usize> + '_ {
+        self.species
+            .iter()
+            .enumerate()
+            .filter(move |(_, cfg)| cfg.hunts.contains(&species))
+            .map(|(idx, _)| idx)
+    }
+}
+
+// Evolutionary state of a single species
+struct Population {
+    ga: GeneticAlgorithm,
+    genealogy: ga::Genealogy,
+    hall_of_fame: ga::HallOfFame,
+    novelty_archive: ga::NoveltyArchive,
+    // Deaths since the last sample, for `EvolutionMode::Continuous`
+    num_dead: u32,
+}
+
+impl Population {
+    fn new(cfg: &SimulationConfig) -> Self {
+        Self {
+            ga: Self::genetic_algorithm(cfg),
+            genealogy: ga::Genealogy::new(),
+            hall_of_fame: ga::HallOfFame::new(cfg.hall_of_fame_size)
+                .with_min_distance(cfg.hall_of_fame_min_distance),
+            novelty_archive: ga::NoveltyArchive::new(
+                cfg.novelty_neighbours,
+                cfg.novelty_archive_size,
+            ),
+            num_dead: 0,
+        }
+    }
+
+    fn genetic_algorithm(cfg: &SimulationConfig) -> GeneticAlgorithm {
+        let ga = ga::GeneticAlgorithm::new(
+            ga::RouletteWheelSelection,
+            ga::UniformCrossover,
+            ga::GaussianMutation::new(0.01, 0.3),
+        );
+
+        if cfg.immigrant_fraction <= 0.0 {
+            return ga;
+        }
+
+        let source = if cfg.immigrants_from_hall_of_fame {
+            ga::ImmigrantSource::HallOfFame
+        } else {
+            // Same range `nn::Network::random()` draws its weights from
+            ga::ImmigrantSource::Random {
+                min: -1.0,
+                max: 1.0,
+            }
+        };
+
+        let trigger = match cfg.immigrant_diversity_threshold {
+            Some(threshold) => ga::ImmigrantTrigger::LowDiversity(threshold),
+            None => ga::ImmigrantTrigger::Always,
+        };
+
+        ga.with_immigrants(
+            ga::Immigrants::new(cfg.immigrant_fraction, source).with_trigger(trigger),
+        )
+    }
 }
 
 pub struct Simulation {
     world: World,
     config: SimulationConfig,
-    prey_ga: ga::GeneticAlgorithm<
-        ga::RouletteWheelSelection,
-        ga::UniformCrossover,
-        ga::GaussianMutation,
-    >,
-    predator_ga: ga::GeneticAlgorithm<
-        ga::RouletteWheelSelection,
-        ga::UniformCrossover,
-        ga::GaussianMutation,
-    >,
+    // One per species, see `SimulationConfig::species`
+    populations: Vec<Population>,
     age: usize,
     generation: usize,
+    // Food that has grown back since the last generation (or sample), see
+    // `SimulationConfig::food_budget`
+    food_regrown: usize,
 }
 
 impl Simulation {
@@ -115,81 +312,176 @@ impl Simulation {
 
     pub fn random_with_config(rng: &mut dyn RngCore, config: SimulationConfig) -> Self {
         let config = config.normalized();
-        let world = Self::random_world_with_config(rng, &config);
-
-        let prey_ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(0.01, 0.3),
-        );
-
-        let predator_ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(0.01, 0.3),
-        );
 
-        Self {
-            world,
+        let mut simulation = Self {
+            world: World::random_with_config(rng, &config),
+            populations: Self::populations(&config),
             config,
-            prey_ga,
-            predator_ga,
             age: 0,
             generation: 0,
-        }
-    }
+            food_regrown: 0,
+        };
 
-    fn random_world_with_config(rng: &mut dyn RngCore, cfg: &SimulationConfig) -> World {
-        let animals = (0..cfg.prey_count)
-            .map(|_| {
-                Animal::random_with_config(
-                    rng,
-                    cfg.prey_photoreceptors,
-                    cfg.prey_fov_angle,
-                    cfg.prey_hidden_neurons,
-                    cfg.prey_speed_multiplier,
-                )
-            })
-            .collect();
-        let predators = (0..cfg.predator_count)
-            .map(|_| {
-                Predator::random_with_config(
-                    rng,
-                    cfg.predator_photoreceptors,
-                    cfg.predator_fov_angle,
-                    cfg.predator_hidden_neurons,
-                    cfg.predator_speed_multiplier,
-                )
-            })
-            .collect();
-        let foods = (0..cfg.food_count).map(|_| Food::random(rng)).collect();
+        simulation.register_founders();
+        simulation
+    }
 
-        World {
-            animals,
-            predators,
-            foods,
-        }
+    fn populations(cfg: &SimulationConfig) -> Vec<Population> {
+        cfg.species.iter().map(|_| Population::new(cfg)).collect()
     }
 
     pub fn reset_with_config(&mut self, rng: &mut dyn RngCore, config: SimulationConfig) {
         self.config = config.normalized();
-        self.world = Self::random_world_with_config(rng, &self.config);
+        self.world = World::random_with_config(rng, &self.config);
+        self.populations = Self::populations(&self.config);
         self.age = 0;
         self.generation = 0;
+        self.food_regrown = 0;
+        self.register_founders();
+    }
+
+    // Makes the current (random) world's agents roots of their species'
+    // pedigrees
+    fn register_founders(&mut self) {
+        for agent in &mut self.world.agents {
+            agent.lineage = Some(self.populations[agent.species].genealogy.founder());
+        }
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    // Following getters take index of a species within
+    // `SimulationConfig::species`
+
+    pub fn genealogy(&self, species: usize) -> &ga::Genealogy {
+        &self.populations[species].genealogy
+    }
+
+    pub fn hall_of_fame(&self, species: usize) -> &ga::HallOfFame {
+        &self.populations[species].hall_of_fame
+    }
+
+    pub fn novelty_archive(&self, species: usize) -> &ga::NoveltyArchive {
+        &self.populations[species].novelty_archive
+    }
+
+    // E.g. to seed this run with genomes archived by another one - they
+    // are reinjected according to `SimulationConfig::hall_of_fame_reinject`
+    pub fn set_hall_of_fame(&mut self, species: usize, hall_of_fame: ga::HallOfFame) {
+        self.populations[species].hall_of_fame = hall_of_fame;
+    }
+
+    pub fn brain_topology(&self, species: usize) -> Vec<usize> {
+        Brain::layer_sizes(
+            self.config.species[species].input_neurons(),
+            self.config.species[species].hidden_neurons,
+        )
+    }
+
+    pub fn best_genome(&self, species: usize) -> Option<ga::Genome> {
+        self.populations[species].hall_of_fame.best().map(|entry| {
+            ga::Genome::new(
+                &self.config.species[species].name,
+                self.brain_topology(species),
+                entry.chromosome.clone(),
+            )
+        })
+    }
+
+    // Replaces a random agent of the genome's species with one built from
+    // `genome`, sparing agents imported before so that importing several
+    // genomes keeps all of them
+    pub fn import_genome(
+        &mut self,
+        rng: &mut dyn RngCore,
+        genome: &ga::Genome,
+    ) -> Result<(), ga::GenomeError> {
+        let Some(species) = self
+            .config
+            .species
+            .iter()
+            .position(|cfg| cfg.name == genome.species)
+        else {
+            return Err(ga::GenomeError::Incompatible(format!(
+                "unknown species `{}`",
+                genome.species
+            )));
+        };
+
+        let topology = self.brain_topology(species);
+
+        if genome.topology != topology {
+            return Err(ga::GenomeError::Incompatible(format!(
+                "brain topology {:?} doesn't match this simulation's {:?}",
+                genome.topology, topology
+            )));
+        }
+
+        let trait_genes = self.config.species[species].trait_genes();
+
+        if genome.chromosome.len() != Brain::weight_count(topology[0], topology[1]) + trait_genes {
+            return Err(ga::GenomeError::Incompatible(format!(
+                "{} genes don't fit brain topology {:?} with {} trait genes",
+                genome.chromosome.len(),
+                topology,
+                trait_genes
+            )));
+        }
+
+        let mut agent =
+            Agent::from_chromosome(genome.chromosome.clone(), &self.config, species, rng);
+
+        agent.lineage = Some(self.populations[species].genealogy.founder());
+        agent.imported = true;
+
+        let candidates: Vec<_> = (0..self.world.agents.len())
+            .filter(|idx| {
+                let agent = &self.world.agents[*idx];
+                agent.species == species && !agent.imported
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            self.world.agents.push(agent);
+        } else {
+            let idx = candidates[rng.gen_range(0..candidates.len())];
+            self.world.agents[idx] = agent;
+        }
+
+        self.world.place_within(rng, self.config.topology);
+
+        Ok(())
+    }
+
     pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
-        self.process_prey_food_collisions(rng);
-        self.process_predator_prey_collisions();
-        self.process_brains();
+        self.world.rebuild_index();
+        self.process_food_collisions();
+        self.process_food_regrowth(rng);
+        self.process_hunting();
+        self.process_brains(rng);
         self.process_movements();
+        self.process_starvation();
 
         self.age += 1;
 
+        if let EvolutionMode::Continuous {
+            sample_interval, ..
+        } = self.config.evolution_mode
+        {
+            self.process_life_cycle(rng);
+
+            return self
+                .age
+                .is_multiple_of(sample_interval)
+                .then(|| self.sample());
+        }
+
         if self.age > GENERATION_LENGTH {
             Some(self.evolve(rng))
         } else {
@@ -206,224 +498,527 @@ impl Simulation {
     }
 
     pub fn current_statistics(&self) -> Statistics {
-        let prey_stats = current_fitness_stats(
-            self.world
-                .animals
-                .iter()
-                .filter(|animal| animal.alive)
-                .map(|animal| 1.0 + animal.satiation as f32),
-        );
-        let predator_stats = current_fitness_stats(
-            self.world
-                .predators
-                .iter()
-                .filter(|predator| predator.alive)
-                .map(|predator| 1.0 + predator.satiation as f32),
-        );
+        let lifespan = self.config.evolution_mode.lifespan();
 
-        let num_dead_prey = self
-            .world
-            .animals
+        let species = self
+            .config
+            .species
             .iter()
-            .filter(|animal| !animal.alive)
-            .count() as u32;
-        let num_dead_predators = self
-            .world
-            .predators
-            .iter()
-            .filter(|predator| !predator.alive)
-            .count() as u32;
+            .enumerate()
+            .map(|(species, cfg)| {
+                let ga = current_fitness_stats(
+                    self.world
+                        .agents_of(species)
+                        .map(|agent| cfg.fitness.fitness(&agent.performance(lifespan))),
+                );
+
+                let (alive, dead): (Vec<_>, Vec<_>) =
+                    self.world.agents_of(species).partition(|agent| agent.alive);
+
+                SpeciesStatistics {
+                    name: cfg.name.clone(),
+                    ga,
+                    num_alive: alive.len() as u32,
+                    num_dead: dead.len() as u32,
+                    morphology: MorphologyStatistics::new(self.world.agents_of(species)),
+                }
+            })
+            .collect();
 
         Statistics {
             generation: self.generation,
-            prey_ga: prey_stats,
-            predator_ga: predator_stats,
-            num_dead_prey,
-            num_dead_predators,
+            species,
+        }
+    }
+
+    // Statistics of `EvolutionMode::Continuous`, where each sample counts
+    // as a generation
+    fn sample(&mut self) -> Statistics {
+        let mut stats = self.current_statistics();
+
+        for (stats, population) in stats.species.iter_mut().zip(&mut self.populations) {
+            stats.num_dead = population.num_dead;
+            population.num_dead = 0;
         }
+
+        self.food_regrown = 0;
+        self.generation += 1;
+
+        stats
     }
 
-    fn process_prey_food_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            if !animal.alive {
+    fn process_food_collisions(&mut self) {
+        let world = &mut self.world;
+        let topology = self.config.topology;
+
+        for agent in &mut world.agents {
+            let cfg = &self.config.species[agent.species];
+
+            if !agent.alive || !cfg.eats_food {
                 continue;
             }
-            for food in &mut self.world.foods {
-                let distance = na::distance(&animal.position, &food.position);
 
-                if distance <= 0.01 {
-                    animal.satiation += 1;
-                    food.position = rng.r#gen();
+            let nearby: Vec<_> = world.food_grid.query(agent.position, agent.reach).collect();
+
+            for idx in nearby {
+                let food = &mut world.foods[idx];
+                let distance = topology.distance(agent.position, food.position);
+
+                if food.is_available() && distance <= agent.reach {
+                    agent.satiation += food.nutrition;
+                    agent.meals += 1;
+                    food.regrowth = Some(self.config.food_types[food.kind].regrowth_ticks);
+
+                    if let Some(metabolism) = &cfg.metabolism {
+                        agent.energy = metabolism.eat(agent.energy, food.nutrition);
+                    }
+                }
+            }
+        }
+    }
+
+    // Counts down eaten food's regrowth timers and grows back those that
+    // are due, as long as there's some budget left
+    fn process_food_regrowth(&mut self, rng: &mut dyn RngCore) {
+        for idx in 0..self.world.foods.len() {
+            match self.world.foods[idx].regrowth {
+                None => continue,
+                Some(0) => (),
+                Some(ticks) => {
+                    self.world.foods[idx].regrowth = Some(ticks - 1);
+                    continue;
                 }
             }
+
+            if self
+                .config
+                .food_budget
+                .is_some_and(|budget| self.food_regrown >= budget)
+            {
+                continue;
+            }
+
+            self.world.foods[idx] = self.world.spawn_food(rng, &self.config);
+            self.food_regrown += 1;
         }
     }
 
-    fn process_predator_prey_collisions(&mut self) {
-        for predator in &mut self.world.predators {
-            if !predator.alive {
+    fn process_hunting(&mut self) {
+        let topology = self.config.topology;
+
+        for hunter_idx in 0..self.world.agents.len() {
+            let hunter = &self.world.agents[hunter_idx];
+
+            if !hunter.alive {
                 continue;
             }
 
-            for animal in &mut self.world.animals {
-                if !animal.alive {
-                    continue;
+            let cfg = &self.config.species[hunter.species];
+
+            // Bigger prey get caught from proportionally further away
+            let caught: Vec<_> = cfg
+                .hunts
+                .iter()
+                .flat_map(|prey| {
+                    let reach = hunter.reach * self.config.species[*prey].max_body_size();
+                    self.world.agent_grids[*prey].query(hunter.position, reach)
+                })
+                .filter(|idx| *idx != hunter_idx)
+                .filter(|idx| {
+                    let prey = &self.world.agents[*idx];
+                    let reach = hunter.reach * prey.body_shape.size;
+
+                    prey.alive && topology.distance(hunter.position, prey.position) <= reach
+                })
+                .collect();
+
+            for idx in caught {
+                self.world.agents[idx].die();
+
+                let hunter = &mut self.world.agents[hunter_idx];
+
+                hunter.satiation += cfg.kill_reward;
+                hunter.kills += 1;
+                hunter.meals += 1;
+
+                if let Some(metabolism) = &cfg.metabolism {
+                    hunter.energy = metabolism.eat(hunter.energy, 1.0);
                 }
 
-                let distance = na::distance(&predator.position, &animal.position);
-                if distance <= 0.012 {
-                    animal.alive = false;
-                    animal.speed = 0.0;
-                    predator.satiation += 3;
+                if let Some(hunger) = &cfg.hunger {
+                    hunter.hunger = hunger.relieve(hunter.hunger);
                 }
             }
         }
     }
 
-    fn process_brains(&mut self) {
-        let predator_positions: Vec<_> = self
-            .world
-            .predators
-            .iter()
-            .filter(|predator| predator.alive)
-            .map(|predator| predator.position)
+    fn process_brains(&mut self, rng: &mut dyn RngCore) {
+        // Everyone looks around first, so that nobody sees others where
+        // they'll only be after this tick
+        let senses: Vec<_> = (0..self.world.agents.len())
+            .map(|idx| {
+                self.world.agents[idx]
+                    .alive
+                    .then(|| self.perceive(rng, idx))
+            })
             .collect();
 
-        for animal in &mut self.world.animals {
-            if !animal.alive {
+        for (agent, senses) in self.world.agents.iter_mut().zip(senses) {
+            let Some((inputs, hunter_distance)) = senses else {
                 continue;
+            };
+
+            let cfg = &self.config.species[agent.species];
+
+            if let Some(distance) = hunter_distance {
+                agent.predator_distance += distance;
+                agent.predator_distance_samples += 1;
             }
-            let mut vision =
-                animal
-                    .eye
-                    .process_vision(animal.position, animal.rotation, &self.world.foods);
-            let predator_vision = animal.eye.process_vision_positions(
-                animal.position,
-                animal.rotation,
-                predator_positions.iter().copied(),
-            );
-            vision.extend(
-                predator_vision
-                    .into_iter()
-                    .map(|cell| cell * PREY_PREDATOR_VISION_GAIN),
+
+            let response = agent.brain.nn.propagate(inputs);
+
+            let speed = response[0].clamp(-cfg.speed_accel, cfg.speed_accel);
+            let rotation = response[1].clamp(-agent.rotation_accel, agent.rotation_accel);
+
+            // Rotation relative to the largest one possible, so that
+            // species turning at different rates stay comparable
+            let turn = if agent.rotation_accel > 0.0 {
+                rotation / agent.rotation_accel
+            } else {
+                0.0
+            };
+
+            agent.speed = (agent.speed + speed).clamp(cfg.speed_min, agent.speed_max);
+            agent.rotation = na::Rotation2::new(agent.rotation.angle() + rotation);
+            agent.trajectory.record(agent.speed, turn);
+
+            // Costs are relative to the species' top speed and rotation, so
+            // that bodies evolved to exceed them pay proportionally more
+            if let Some(metabolism) = &cfg.metabolism {
+                agent.energy -= metabolism.cost(
+                    agent.speed / cfg.speed_max.max(f32::EPSILON),
+                    turn * agent.body_shape.agility,
+                ) + agent.upkeep();
+            }
+        }
+    }
+
+    // Returns brain inputs of agent with given index, along with distance
+    // to the closest agent hunting it (if there's any)
+    fn perceive(&self, rng: &mut dyn RngCore, idx: usize) -> (Vec<f32>, Option<f32>) {
+        let world = &self.world;
+        let agent = &world.agents[idx];
+        let cfg = &self.config.species[agent.species];
+
+        let mut inputs = Vec::new();
+
+        for (channel, eye) in cfg.channels.iter().zip(&agent.eyes) {
+            let (position, rotation) = (agent.position, agent.rotation);
+
+            let vision = match self.sightings(idx, channel.kind, eye.fov_range()) {
+                Some((sightings, kinds)) => match cfg.eye_mode {
+                    EyeMode::Summed => eye.process_vision_positions(
+                        position,
+                        rotation,
+                        sightings.iter().map(|sighting| sighting.position),
+                    ),
+
+                    EyeMode::Nearest {
+                        occlusion,
+                        object_types,
+                    } => eye.process_nearest(
+                        position,
+                        rotation,
+                        &sightings,
+                        occlusion,
+                        object_types.then_some(kinds),
+                    ),
+                },
+
+                None => eye.process_obstacles(position, rotation, &world.obstacles),
+            };
+
+            inputs.extend(vision.into_iter().map(|cell| cell * channel.gain));
+        }
+
+        let senses = cfg.senses;
+
+        if senses.energy {
+            inputs.push(agent.energy);
+        }
+
+        if senses.hunger {
+            inputs.push(
+                cfg.hunger
+                    .map_or(0.0, |hunger| hunger.fraction(agent.hunger)),
             );
+        }
 
-            let response = animal.brain.nn.propagate(vision);
+        if senses.speed {
+            inputs.push(agent.speed / agent.speed_max.max(f32::EPSILON));
+        }
 
-            let speed = response[0].clamp(-PREY_SPEED_ACCEL, PREY_SPEED_ACCEL);
-            let rotation = response[1].clamp(-PREY_ROTATION_ACCEL, PREY_ROTATION_ACCEL);
+        if senses.age {
+            inputs.push(self.age_fraction(agent));
+        }
 
-            let prey_speed_min = PREY_SPEED_MIN * self.config.prey_speed_multiplier;
-            let prey_speed_max = PREY_SPEED_MAX * self.config.prey_speed_multiplier;
-            animal.speed = (animal.speed + speed).clamp(prey_speed_min, prey_speed_max);
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+        if senses.bias {
+            inputs.push(1.0);
         }
 
-        let prey_positions: Vec<_> = self
-            .world
-            .animals
+        if senses.noise {
+            inputs.push(rng.gen_range(-1.0..=1.0));
+        }
+
+        (inputs, self.hunter_distance(idx))
+    }
+
+    // Distance from agent with given index to the closest of its hunters,
+    // capped at how far it can see (but no less than the default eye range),
+    // so that only those nearby have to be checked; `None` if nobody hunts
+    // its species
+    fn hunter_distance(&self, idx: usize) -> Option<f32> {
+        let world = &self.world;
+        let agent = &world.agents[idx];
+        let hunters: Vec<_> = self.config.hunters(agent.species).collect();
+
+        if hunters.is_empty() {
+            return None;
+        }
+
+        let radius = agent
+            .eyes
             .iter()
-            .filter(|animal| animal.alive)
-            .map(|animal| animal.position)
-            .collect();
+            .map(Eye::fov_range)
+            .fold(FOV_RANGE, f32::max);
 
-        for predator in &mut self.world.predators {
-            if !predator.alive {
-                continue;
+        let closest = hunters
+            .iter()
+            .flat_map(|hunter| world.agent_grids[*hunter].query(agent.position, radius))
+            .filter(|other| *other != idx)
+            .map(|other| &world.agents[other])
+            .filter(|other| other.alive)
+            .map(|other| {
+                self.config
+                    .topology
+                    .distance(other.position, agent.position)
+            })
+            .fold(radius, f32::min);
+
+        Some(closest)
+    }
+
+    // Returns what agent with given index sees through a channel of given
+    // kind, along with how many kinds of objects there are - or `None` for
+    // `ChannelKind::Obstacles`, which sees no objects
+    fn sightings(
+        &self,
+        idx: usize,
+        kind: ChannelKind,
+        fov_range: f32,
+    ) -> Option<(Vec<Sighting>, usize)> {
+        let world = &self.world;
+        let topology = self.config.topology;
+        let agent = &world.agents[idx];
+        let position = agent.position;
+
+        let in_view = |sighting: &Sighting| in_sight(&world.obstacles, position, sighting.position);
+
+        // Living agents of given species that are in sight
+        let visible = |species: usize| {
+            world.agent_grids[species]
+                .query(position, fov_range)
+                .filter(move |other| *other != idx)
+                .map(|other| &world.agents[other])
+                .filter(|other| other.alive)
+                .map(move |other| Sighting {
+                    position: topology.image(position, other.position),
+                    radius: other.body_radius,
+                    kind: species,
+                })
+                .filter(in_view)
+        };
+
+        let num_species = self.config.species.len();
+
+        let sightings = match kind {
+            ChannelKind::Food => {
+                let sightings = world
+                    .food_grid
+                    .query(position, fov_range)
+                    .map(|idx| &world.foods[idx])
+                    .filter(|food| food.is_available())
+                    .map(|food| Sighting {
+                        position: topology.image(position, food.position),
+                        radius: FOOD_RADIUS,
+                        kind: food.kind,
+                    })
+                    .filter(in_view)
+                    .collect();
+
+                return Some((sightings, self.config.food_types.len()));
             }
 
-            let vision = predator.eye.process_vision_positions(
-                predator.position,
-                predator.rotation,
-                prey_positions.iter().copied(),
-            );
+            ChannelKind::Prey => self.config.species[agent.species]
+                .hunts
+                .iter()
+                .flat_map(|prey| visible(*prey))
+                .collect(),
 
-            let response = predator.brain.nn.propagate(vision);
-            let speed = response[0].clamp(-PREDATOR_SPEED_ACCEL, PREDATOR_SPEED_ACCEL);
-            let rotation = response[1].clamp(-PREDATOR_ROTATION_ACCEL, PREDATOR_ROTATION_ACCEL);
+            ChannelKind::Predators => self
+                .config
+                .hunters(agent.species)
+                .flat_map(visible)
+                .collect(),
 
-            let predator_speed_min = PREDATOR_SPEED_MIN * self.config.predator_speed_multiplier;
-            let predator_speed_max = PREDATOR_SPEED_MAX * self.config.predator_speed_multiplier;
-            predator.speed = (predator.speed + speed).clamp(predator_speed_min, predator_speed_max);
-            predator.rotation = na::Rotation2::new(predator.rotation.angle() + rotation);
-        }
+            ChannelKind::SameSpecies => visible(agent.species).collect(),
+
+            ChannelKind::Obstacles => return None,
+        };
+
+        Some((sightings, num_species))
     }
 
-    fn process_movements(&mut self) {
-        for animal in &mut self.world.animals {
-            if !animal.alive {
+    // Returns how much of given agent's time is up, see `Senses::age`
+    fn age_fraction(&self, agent: &Agent) -> f32 {
+        let age = match self.config.evolution_mode {
+            EvolutionMode::Generational => self.age,
+            EvolutionMode::Continuous { .. } => agent.ticks_alive,
+        };
+
+        (age as f32 / self.config.evolution_mode.lifespan() as f32).min(1.0)
+    }
+
+    fn process_starvation(&mut self) {
+        for agent in &mut self.world.agents {
+            if !agent.alive {
                 continue;
             }
-            animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
 
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+            if agent.energy <= 0.0 {
+                agent.energy = 0.0;
+                agent.die();
+            }
+
+            if let Some(hunger) = &self.config.species[agent.species].hunger {
+                agent.hunger = hunger.grow(agent.hunger);
+
+                if hunger.is_starving(agent.hunger) {
+                    agent.die();
+                }
+            }
         }
+    }
 
-        for predator in &mut self.world.predators {
-            if !predator.alive {
+    fn process_movements(&mut self) {
+        for agent in &mut self.world.agents {
+            if !agent.alive {
                 continue;
             }
 
-            predator.position += predator.rotation * na::Vector2::new(0.0, predator.speed);
-            predator.position.x = na::wrap(predator.position.x, 0.0, 1.0);
-            predator.position.y = na::wrap(predator.position.y, 0.0, 1.0);
+            agent.position += agent.rotation * na::Vector2::new(0.0, agent.speed);
+            agent.ticks_alive += 1;
+
+            self.config
+                .topology
+                .confine(&mut agent.position, &mut agent.rotation);
+
+            collide_with_obstacles(
+                &self.world.obstacles,
+                self.config.obstacle_response,
+                agent.body_radius,
+                &mut agent.position,
+                &mut agent.rotation,
+            );
         }
     }
 
     fn evolve(&mut self, rng: &mut dyn RngCore) -> Statistics {
         self.age = 0;
 
-        let num_dead_prey = self
-            .world
-            .animals
-            .iter()
-            .filter(|animal| !animal.alive)
-            .count() as u32;
+        let mut agents = Vec::new();
+        let mut species = Vec::new();
 
-        let num_dead_predators = self
-            .world
-            .predators
-            .iter()
-            .filter(|predator| !predator.alive)
-            .count() as u32;
+        for idx in 0..self.config.species.len() {
+            let (offspring, stats) = self.evolve_species(rng, idx);
 
-        let alive_prey: Vec<_> = self
-            .world
-            .animals
-            .iter()
-            .filter(|animal| animal.alive)
-            .collect();
-        let prey_population: Vec<_> = if alive_prey.is_empty() {
-            Vec::new()
-        } else {
-            (0..self.config.prey_count)
-                .map(|i| AnimalIndividual::from_animal(alive_prey[i % alive_prey.len()]))
-                .collect()
-        };
+            agents.extend(offspring);
+            species.push(stats);
+        }
 
-        let predator_population: Vec<_> = self
-            .world
-            .predators
-            .iter()
-            .filter(|predator| predator.alive)
-            .map(PredatorIndividual::from_predator)
-            .collect();
+        self.world.agents = agents;
 
-        let (evolved_prey, prey_stats) = if prey_population.is_empty() {
-            (
-                Vec::new(),
-                ga::Statistics {
-                    min_fitness: 0.0,
-                    max_fitness: 0.0,
-                    avg_fitness: 0.0,
-                    median_fitness: 0.0,
-                },
-            )
-        } else {
-            self.prey_ga.evolve(rng, &prey_population)
-        };
-        let (evolved_predators, predator_stats) = if predator_population.is_empty() {
+        for idx in 0..self.world.foods.len() {
+            self.world.foods[idx] = self.world.spawn_food(rng, &self.config);
+        }
+
+        self.food_regrown = 0;
+
+        self.world.place_within(rng, self.config.topology);
+
+        let generation = self.generation;
+        self.generation += 1;
+
+        Statistics {
+            generation,
+            species,
+        }
+    }
+
+    // Breeds the next generation of given species out of the current one
+    fn evolve_species(
+        &mut self,
+        rng: &mut dyn RngCore,
+        species: usize,
+    ) -> (Vec<Agent>, SpeciesStatistics) {
+        let weight_bounds = self.weight_bounds();
+        let count = self.next_generation_size(species);
+        let lifespan = self.config.evolution_mode.lifespan();
+
+        let num_dead = self
+            .world
+            .agents_of(species)
+            .filter(|agent| !agent.alive)
+            .count() as u32;
+
+        let morphology = MorphologyStatistics::new(self.world.agents_of(species));
+
+        // The dead take part as well, with fitness reflecting how long they
+        // managed to last
+        let mut population: Vec<_> = self
+            .world
+            .agents_of(species)
+            .map(|agent| {
+                AgentIndividual::from_agent(
+                    agent,
+                    self.config.species[species].fitness.as_ref(),
+                    lifespan,
+                )
+                .with_bounds(weight_bounds.as_ref())
+            })
+            .collect();
+
+        self.populations[species]
+            .hall_of_fame
+            .update(&population, self.generation);
+
+        // Statistics always describe the configured fitness, as it was
+        // before being made selectable or blended with novelty
+        let fitness_stats = (!population.is_empty()).then(|| ga::Statistics::new(&population));
+
+        if self.config.species[species].novelty_weight > 0.0 && !population.is_empty() {
+            self.score_novelty(species, &mut population);
+        }
+
+        make_selectable(
+            population
+                .iter_mut()
+                .map(|individual| &mut individual.fitness),
+        );
+
+        let pop = &mut self.populations[species];
+
+        let (mut evolved, stats) = if population.is_empty() {
             (
                 Vec::new(),
                 ga::Statistics {
@@ -431,54 +1026,243 @@ impl Simulation {
                     max_fitness: 0.0,
                     avg_fitness: 0.0,
                     median_fitness: 0.0,
+                    bound_hits: 0,
                 },
             )
         } else {
-            self.predator_ga.evolve(rng, &predator_population)
+            pop.ga.evolve_to_with_hall_of_fame(
+                rng,
+                &population,
+                count,
+                &mut pop.genealogy,
+                &pop.hall_of_fame,
+            )
         };
 
-        self.world.animals = evolved_prey
+        pop.hall_of_fame.reinject(
+            rng,
+            &mut evolved,
+            self.config.hall_of_fame_reinject,
+            Some(&mut pop.genealogy),
+        );
+
+        let offspring = evolved
             .into_iter()
-            .map(|individual| {
-                individual.into_animal(
-                    self.config.prey_photoreceptors,
-                    self.config.prey_fov_angle,
-                    self.config.prey_hidden_neurons,
-                    self.config.prey_speed_multiplier,
-                    rng,
-                )
-            })
+            .map(|individual| individual.into_agent(&self.config, species, rng))
             .collect();
-        self.world.predators = evolved_predators
+
+        let stats = SpeciesStatistics {
+            name: self.config.species[species].name.clone(),
+            ga: with_raw_fitness(stats, fitness_stats),
+            num_alive: population.len() as u32 - num_dead,
+            num_dead,
+            morphology,
+        };
+
+        (offspring, stats)
+    }
+
+    fn weight_bounds(&self) -> Option<Arc<ga::Bounds>> {
+        self.config.weight_bound.map(|bound| {
+            Arc::new(ga::Bounds::global(
+                -bound,
+                bound,
+                self.config.weight_bound_policy,
+            ))
+        })
+    }
+
+    // Retires the dead and lets everyone with enough energy reproduce, see
+    // `EvolutionMode::Continuous`
+    fn process_life_cycle(&mut self, rng: &mut dyn RngCore) {
+        let EvolutionMode::Continuous {
+            reproduction_energy,
+            mate_radius,
+            max_age,
+            ..
+        } = self.config.evolution_mode
+        else {
+            return;
+        };
+
+        let weight_bounds = self.weight_bounds();
+
+        // Retiring the dead
+        for agent in &mut self.world.agents {
+            if agent.ticks_alive >= max_age {
+                agent.alive = false;
+            }
+        }
+
+        let (agents, dead): (Vec<_>, Vec<_>) = std::mem::take(&mut self.world.agents)
             .into_iter()
-            .map(|individual| {
-                individual.into_predator(
-                    self.config.predator_photoreceptors,
-                    self.config.predator_fov_angle,
-                    self.config.predator_hidden_neurons,
-                    self.config.predator_speed_multiplier,
-                    rng,
-                )
-            })
+            .partition(|agent| agent.alive);
+
+        self.world.agents = agents;
+
+        // Mates are looked up through the index, which mustn't point at the
+        // dead (nor at where others were before moving)
+        self.world.rebuild_index();
+
+        for (species, population) in self.populations.iter_mut().enumerate() {
+            let dead: Vec<_> = dead
+                .iter()
+                .filter(|agent| agent.species == species)
+                .map(|agent| {
+                    AgentIndividual::from_agent(
+                        agent,
+                        self.config.species[species].fitness.as_ref(),
+                        max_age,
+                    )
+                })
+                .collect();
+
+            record_fitness(&mut population.genealogy, &dead);
+            population.hall_of_fame.update(&dead, self.generation);
+            population.num_dead += dead.len() as u32;
+        }
+
+        // Reproducing
+        let mut counts = vec![0; self.config.species.len()];
+
+        for agent in &self.world.agents {
+            counts[agent.species] += 1;
+        }
+
+        let mut newborns = Vec::new();
+
+        for idx in 0..self.world.agents.len() {
+            let parent = &self.world.agents[idx];
+            let species = parent.species;
+            let cfg = &self.config.species[species];
+
+            if counts[species] >= cfg.max_count || parent.energy < reproduction_energy {
+                continue;
+            }
+
+            let mate = self.world.agent_grids[species]
+                .query(parent.position, mate_radius)
+                .filter(|mate| *mate != idx)
+                .map(|mate| &self.world.agents[mate])
+                .map(|mate| {
+                    (
+                        mate,
+                        self.config
+                            .topology
+                            .distance(mate.position, parent.position),
+                    )
+                })
+                .filter(|(_, distance)| *distance <= mate_radius)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(mate, _)| AgentIndividual::from_agent(mate, cfg.fitness.as_ref(), max_age));
+
+            let population = &mut self.populations[species];
+
+            let child = population.ga.breed(
+                rng,
+                &AgentIndividual::from_agent(parent, cfg.fitness.as_ref(), max_age)
+                    .with_bounds(weight_bounds.as_ref()),
+                mate.as_ref(),
+                Some(&mut population.genealogy),
+            );
+
+            let mut child = child.into_agent(&self.config, species, rng);
+
+            child.position = parent.position;
+            child.energy = parent.energy / 2.0;
+
+            self.world.agents[idx].energy = child.energy;
+            counts[species] += 1;
+            newborns.push(child);
+        }
+
+        self.world.agents.extend(newborns);
+
+        // Replacing extinct species
+        for (species, count) in counts.into_iter().enumerate() {
+            if count > 0 {
+                continue;
+            }
+
+            let mut agents = World::random_agents(rng, &self.config, species);
+
+            for agent in &mut agents {
+                agent.lineage = Some(self.populations[species].genealogy.founder());
+            }
+
+            self.world.agents.extend(agents);
+        }
+
+        self.world.place_within(rng, self.config.topology);
+    }
+
+    // Blends fitness of given species' agents with novelty of their
+    // behaviour; only used by the generational mode, see
+    // `SpeciesConfig::novelty_weight`
+    fn score_novelty(&mut self, species: usize, population: &mut [AgentIndividual]) {
+        let cfg = &self.config.species[species];
+        let max_path_length = GENERATION_LENGTH as f32 * cfg.max_agent_speed();
+
+        let behaviours: Vec<_> = self
+            .world
+            .agents_of(species)
+            .map(|agent| agent.trajectory.behaviour(agent.position, max_path_length))
             .collect();
 
-        for food in &mut self.world.foods {
-            food.position = rng.r#gen();
+        let novelty = self.populations[species].novelty_archive.score(&behaviours);
+        let objective = ga::WeightedObjective::new(cfg.novelty_weight);
+
+        for (individual, novelty) in population.iter_mut().zip(novelty) {
+            individual.fitness = objective.score(individual.fitness, novelty);
         }
+    }
 
-        let generation = self.generation;
-        self.generation += 1;
+    // Returns how many agents the next generation of given species should
+    // have, see `PopulationDynamics`
+    fn next_generation_size(&self, species: usize) -> usize {
+        let cfg = &self.config.species[species];
 
-        Statistics {
-            generation,
-            prey_ga: prey_stats,
-            predator_ga: predator_stats,
-            num_dead_prey,
-            num_dead_predators,
+        match cfg.population_dynamics {
+            PopulationDynamics::Fixed => cfg.count,
+
+            PopulationDynamics::Resources { offspring_per_meal } => {
+                let meals: usize = self
+                    .world
+                    .agents_of(species)
+                    .filter(|agent| agent.alive)
+                    .map(|agent| agent.meals)
+                    .sum();
+
+                let count = (meals as f32 * offspring_per_meal).round() as usize;
+
+                count.clamp(1, cfg.max_count)
+            }
         }
     }
 }
 
+fn record_fitness<I>(genealogy: &mut ga::Genealogy, individuals: &[I])
+where
+    I: ga::Individual,
+{
+    for individual in individuals {
+        if let Some(lineage) = individual.lineage() {
+            genealogy.record_fitness(lineage.id, individual.fitness());
+        }
+    }
+}
+
+// Keeps `bound_hits` of the offspring, but fitness of their parents
+fn with_raw_fitness(evolved: ga::Statistics, raw: Option<ga::Statistics>) -> ga::Statistics {
+    match raw {
+        Some(raw) => ga::Statistics {
+            bound_hits: evolved.bound_hits,
+            ..raw
+        },
+        None => evolved,
+    }
+}
+
 fn current_fitness_stats(values: impl Iterator<Item = f32>) -> ga::Statistics {
     let mut fitnesses = Vec::new();
     let mut sum = 0.0f32;
@@ -498,6 +1282,7 @@ fn current_fitness_stats(values: impl Iterator<Item = f32>) -> ga::Statistics {
             max_fitness: 0.0,
             avg_fitness: 0.0,
             median_fitness: 0.0,
+            bound_hits: 0,
         }
     } else {
         fitnesses.sort_by(|a, b| a.total_cmp(b));
@@ -513,6 +1298,834 @@ fn current_fitness_stats(values: impl Iterator<Item = f32>) -> ga::Statistics {
             max_fitness: max,
             avg_fitness: sum / (fitnesses.len() as f32),
             median_fitness,
+            bound_hits: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn rng() -> ChaCha8Rng {
+        ChaCha8Rng::from_seed(Default::default())
+    }
+
+    // Agent with given trait genes and a brain that does nothing, i.e.
+    // keeps going straight at the initial speed
+    fn idle_agent(sim: &Simulation, species: usize, genes: &[f32]) -> Agent {
+        let cfg = &sim.config.species[species];
+        let weights = Brain::weight_count(cfg.input_neurons(), cfg.hidden_neurons);
+
+        let chromosome = std::iter::repeat_n(0.0, weights)
+            .chain(genes.iter().copied())
+            .collect();
+
+        Agent::from_chromosome(chromosome, &sim.config, species, &mut rng())
+    }
+
+    fn agent_at(sim: &Simulation, species: usize, x: f32, y: f32) -> Agent {
+        let mut agent = idle_agent(sim, species, &[]);
+        agent.position = na::Point2::new(x, y);
+        agent
+    }
+
+    #[test]
+    fn hunter_distance() {
+        let mut sim = Simulation::random(&mut rng());
+
+        sim.world.agents = vec![
+            agent_at(&sim, 0, 0.5, 0.5),
+            agent_at(&sim, 1, 0.6, 0.5),
+            agent_at(&sim, 1, 0.5, 0.45),
+        ];
+        sim.world.rebuild_index();
+
+        assert!((sim.hunter_distance(0).unwrap() - 0.05).abs() < 1e-5);
+        assert_eq!(sim.hunter_distance(1), None);
+
+        // Hunters out of sight are as good as the furthest ones it can see
+        sim.world.agents[1].position = na::Point2::new(0.0, 0.0);
+        sim.world.agents[2].alive = false;
+        sim.world.rebuild_index();
+
+        assert_eq!(sim.hunter_distance(0), Some(FOV_RANGE));
+    }
+
+    #[test]
+    fn imported_genomes_dont_replace_each_other() {
+        let config = SimulationConfig {
+            species: vec![SpeciesConfig {
+                count: 3,
+                ..SpeciesConfig::prey()
+            }],
+            ..Default::default()
+        };
+
+        let mut rng = rng();
+        let mut sim = Simulation::random_with_config(&mut rng, config);
+        let topology = sim.brain_topology(0);
+        let weights = Brain::weight_count(topology[0], topology[1]);
+
+        for gene in 1..=4 {
+            let genome = ga::Genome::new(
+                "prey",
+                topology.clone(),
+                std::iter::repeat_n(gene as f32, weights).collect(),
+            );
+
+            sim.import_genome(&mut rng, &genome).unwrap();
+        }
+
+        // Once every agent has been imported, further genomes join them
+        let mut imported: Vec<_> = sim
+            .world
+            .agents
+            .iter()
+            .map(|agent| {
+                assert!(agent.imported);
+                agent.brain.as_chromosome()[0]
+            })
+            .collect();
+        imported.sort_by(f32::total_cmp);
+
+        assert_eq!(imported, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    // Prey, mesopredators hunting them and apex predators hunting the
+    // mesopredators
+    fn food_web() -> SimulationConfig {
+        SimulationConfig {
+            species: vec![
+                SpeciesConfig {
+                    count: 12,
+                    ..SpeciesConfig::prey()
+                },
+                SpeciesConfig {
+                    name: "mesopredator".into(),
+                    count: 6,
+                    ..SpeciesConfig::predator(0)
+                },
+                SpeciesConfig {
+                    name: "apex".into(),
+                    count: 3,
+                    ..SpeciesConfig::predator(1)
+                },
+            ],
+            food_count: 20,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hunters_catch_prey_within_reach() {
+        let mut sim = Simulation::random(&mut rng());
+        let reach = sim.config.species[1].reach;
+
+        sim.world.agents = vec![
+            agent_at(&sim, 0, 0.5, 0.5),
+            agent_at(&sim, 0, 0.5 + reach * 2.0, 0.5),
+            agent_at(&sim, 1, 0.5 + reach * 0.5, 0.5),
+        ];
+        sim.world.agents[2].hunger = 0.5;
+        sim.world.rebuild_index();
+        sim.process_hunting();
+
+        let [near, far, hunter] = [0, 1, 2].map(|idx| &sim.world.agents[idx]);
+        let cfg = &sim.config.species[1];
+
+        assert!(!near.alive);
+        assert!(far.alive);
+        assert_eq!(hunter.kills, 1);
+        assert_eq!(hunter.meals, 1);
+        assert_eq!(hunter.satiation, cfg.kill_reward);
+        assert_eq!(hunter.hunger, cfg.hunger.unwrap().relieve(0.5));
+    }
+
+    #[test]
+    fn mesopredators_hunt_and_get_hunted() {
+        let mut sim = Simulation::random_with_config(&mut rng(), food_web());
+
+        assert_eq!(sim.config.hunters(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(sim.config.hunters(1).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(sim.config.hunters(2).count(), 0);
+
+        sim.world.agents = vec![
+            agent_at(&sim, 0, 0.5, 0.5),
+            agent_at(&sim, 1, 0.505, 0.5),
+            agent_at(&sim, 2, 0.51, 0.5),
+        ];
+        sim.world.rebuild_index();
+        sim.process_hunting();
+
+        let [prey, mesopredator, apex] = [0, 1, 2].map(|idx| &sim.world.agents[idx]);
+
+        // Apex predators don't eat prey, only mesopredators do
+        assert!(!prey.alive);
+        assert!(!mesopredator.alive);
+        assert!(apex.alive);
+        assert_eq!(mesopredator.kills, 1);
+        assert_eq!(apex.kills, 1);
+    }
+
+    #[test]
+    fn resources_determine_next_generation_size() {
+        let mut config = SimulationConfig::default();
+        config.species[0].max_count = 10;
+        config.species[0].population_dynamics = PopulationDynamics::Resources {
+            offspring_per_meal: 2.0,
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng(), config);
+        let meals = |sim: &mut Simulation, meals: [usize; 3]| {
+            sim.world.agents = meals
+                .iter()
+                .map(|&meals| Agent {
+                    meals,
+                    ..agent_at(sim, 0, 0.5, 0.5)
+                })
+                .collect();
+
+            // The dead don't breed
+            sim.world.agents[2].alive = false;
+            sim.next_generation_size(0)
+        };
+
+        assert_eq!(meals(&mut sim, [2, 1, 5]), 6);
+        assert_eq!(meals(&mut sim, [0, 0, 5]), 1);
+        assert_eq!(meals(&mut sim, [4, 3, 0]), 10);
+        assert_eq!(sim.next_generation_size(1), sim.config.species[1].count);
+    }
+
+    #[test]
+    fn fast_forward_food_web() {
+        let mut rng = rng();
+        let mut config = food_web();
+
+        config.species[0].population_dynamics = PopulationDynamics::Resources {
+            offspring_per_meal: 0.5,
+        };
+        config.species[1].eye_mode = EyeMode::Nearest {
+            occlusion: false,
+            object_types: true,
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config);
+
+        for generation in 0..2 {
+            let stats = sim.fast_forward(&mut rng);
+
+            assert_eq!(stats.generation, generation);
+            assert_eq!(stats.species.len(), 3);
+            assert!(stats.species("apex").is_some());
+
+            let prey = sim.world.agents_of(0).count();
+            assert!((1..=sim.config.species[0].max_count).contains(&prey));
+            assert_eq!(sim.world.agents_of(1).count(), 6);
+            assert_eq!(sim.world.agents_of(2).count(), 3);
+            assert!(sim.world.agents.iter().all(|agent| agent.alive));
         }
     }
+
+    // Prey right next to a predator whose hunger grows by an eighth each
+    // tick
+    fn hungry_predator() -> Simulation {
+        let mut config = SimulationConfig::default();
+        config.species[1].hunger = Some(Hunger {
+            rate: 0.125,
+            ..Hunger::default()
+        });
+
+        let mut sim = Simulation::random_with_config(&mut rng(), config);
+
+        sim.world.agents = vec![agent_at(&sim, 0, 0.5, 0.5), agent_at(&sim, 1, 0.9, 0.9)];
+        sim
+    }
+
+    #[test]
+    fn predators_starve_without_kills() {
+        let mut sim = hungry_predator();
+
+        for _ in 0..8 {
+            sim.process_starvation();
+        }
+
+        assert!(sim.world.agents[1].alive);
+
+        sim.process_starvation();
+
+        assert!(!sim.world.agents[1].alive);
+        assert!(sim.world.agents[0].alive, "prey don't get hungry");
+
+        let stats = sim.current_statistics();
+        let predators = stats.species(PREDATOR_SPECIES).unwrap();
+
+        assert_eq!((predators.num_alive, predators.num_dead), (0, 1));
+
+        let stats = sim.evolve(&mut rng());
+        assert_eq!(stats.species(PREDATOR_SPECIES).unwrap().num_dead, 1);
+        assert_eq!(stats.species(PREY_SPECIES).unwrap().num_dead, 0);
+    }
+
+    #[test]
+    fn kills_keep_predators_alive() {
+        let mut sim = hungry_predator();
+
+        for _ in 0..8 {
+            sim.process_starvation();
+        }
+
+        sim.world.agents[1].position = na::Point2::new(0.505, 0.5);
+        sim.world.rebuild_index();
+        sim.process_hunting();
+
+        assert_eq!(sim.world.agents[1].hunger, 0.25);
+
+        for _ in 0..6 {
+            sim.process_starvation();
+        }
+
+        assert!(sim.world.agents[1].alive);
+    }
+
+    #[test]
+    fn moving_costs_energy() {
+        let mut rng = rng();
+
+        let config = SimulationConfig {
+            species: vec![SpeciesConfig {
+                eats_food: false,
+                metabolism: Some(Metabolism::default()),
+                ..SpeciesConfig::prey()
+            }],
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config);
+        let cfg = &sim.config.species[0];
+        let (speed_min, speed_max) = (cfg.speed_min, cfg.speed_max);
+
+        let slow = Agent {
+            speed: speed_min,
+            ..idle_agent(&sim, 0, &[])
+        };
+
+        let fast = Agent {
+            speed: speed_max,
+            ..idle_agent(&sim, 0, &[])
+        };
+
+        // Bias of the output neuron responsible for rotation
+        let mut turning = slow.as_chromosome().into_iter().collect::<Vec<_>>();
+        let bias = turning.len() - cfg.hidden_neurons - 1;
+        turning[bias] = 1.0;
+
+        let turning = Agent {
+            speed: speed_min,
+            ..Agent::from_chromosome(turning.into_iter().collect(), &sim.config, 0, &mut rng)
+        };
+
+        sim.world.agents = vec![slow, fast, turning];
+
+        for _ in 0..50 {
+            sim.step(&mut rng);
+        }
+
+        let [slow, fast, turning] = [0, 1, 2].map(|idx| &sim.world.agents[idx]);
+
+        assert!(slow.energy < 1.0);
+        assert!(fast.energy < slow.energy);
+        assert!(turning.energy < slow.energy);
+        assert_ne!(turning.rotation, slow.rotation);
+        assert_eq!(fast.speed, speed_max);
+    }
+
+    #[test]
+    fn agents_die_when_out_of_energy() {
+        let config = SimulationConfig {
+            species: vec![SpeciesConfig {
+                metabolism: Some(Metabolism::default()),
+                ..SpeciesConfig::prey()
+            }],
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng(), config);
+
+        sim.world.agents = vec![
+            Agent {
+                energy: 0.001,
+                ..idle_agent(&sim, 0, &[])
+            },
+            Agent {
+                energy: -0.1,
+                ..idle_agent(&sim, 0, &[])
+            },
+        ];
+        sim.process_starvation();
+
+        assert!(sim.world.agents[0].alive);
+        assert!(!sim.world.agents[1].alive);
+        assert_eq!(sim.world.agents[1].energy, 0.0);
+    }
+
+    // Prey and predators evolving continuously; nobody moves nor eats
+    fn continuous() -> Simulation {
+        let mut config = SimulationConfig {
+            evolution_mode: EvolutionMode::Continuous {
+                reproduction_energy: 0.8,
+                mate_radius: 0.1,
+                max_age: 100,
+                sample_interval: 10,
+            },
+            ..Default::default()
+        };
+
+        config.species[0].max_count = 4;
+        config.species[0].eats_food = false;
+        config.species[0].speed_min = 0.0;
+        config.species[0].initial_speed = 0.0;
+
+        Simulation::random_with_config(&mut rng(), config)
+    }
+
+    fn founder(sim: &mut Simulation, mut agent: Agent, energy: f32) -> Agent {
+        agent.lineage = Some(sim.populations[agent.species].genealogy.founder());
+        agent.energy = energy;
+        agent
+    }
+
+    fn parents(agent: &Agent) -> Vec<ga::IndividualId> {
+        agent.lineage.as_ref().unwrap().parents.clone()
+    }
+
+    #[test]
+    fn continuous_reproduction() {
+        let mut sim = continuous();
+
+        let rich = agent_at(&sim, 0, 0.5, 0.5);
+        let rich = founder(&mut sim, rich, 0.9);
+        let mate = agent_at(&sim, 0, 0.55, 0.5);
+        let mate = founder(&mut sim, mate, 0.5);
+        let loner = agent_at(&sim, 0, 0.9, 0.9);
+        let loner = founder(&mut sim, loner, 0.8);
+        let predator = agent_at(&sim, 1, 0.1, 0.1);
+        let predator = founder(&mut sim, predator, 0.5);
+
+        sim.world.agents = vec![rich, mate, loner, predator];
+        sim.process_life_cycle(&mut rng());
+
+        // The first one breeds with its closest mate, the second one is too
+        // hungry and the third one doesn't fit within `max_count` anymore
+        let agents = &sim.world.agents;
+        let child = &agents[4];
+
+        assert_eq!(agents.len(), 5);
+        assert_eq!(child.species, 0);
+        assert_eq!(child.position, agents[0].position);
+        assert_eq!(child.energy, 0.45);
+        assert_eq!(agents[0].energy, 0.45);
+        assert_eq!([agents[1].energy, agents[2].energy], [0.5, 0.8]);
+
+        let mut expected = vec![
+            agents[0].lineage.as_ref().unwrap().id,
+            agents[1].lineage.as_ref().unwrap().id,
+        ];
+        let mut actual = parents(child);
+
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+
+        // Without anyone around, the parent gets mutated on its own
+        sim.world.agents[0].position = na::Point2::new(0.3, 0.3);
+        sim.world.agents[0].energy = 0.9;
+        sim.world.agents.truncate(2);
+        sim.process_life_cycle(&mut rng());
+
+        let child = &sim.world.agents[2];
+        assert_eq!(
+            parents(child),
+            vec![sim.world.agents[0].lineage.as_ref().unwrap().id]
+        );
+    }
+
+    #[test]
+    fn continuous_retirement_and_reseeding() {
+        let mut sim = continuous();
+
+        let prey = Agent {
+            energy: 0.5,
+            ..agent_at(&sim, 0, 0.5, 0.5)
+        };
+        let old = Agent {
+            ticks_alive: 100,
+            ..agent_at(&sim, 0, 0.6, 0.5)
+        };
+        let dead_predator = Agent {
+            alive: false,
+            ..agent_at(&sim, 1, 0.1, 0.1)
+        };
+
+        sim.world.agents = vec![prey, old, dead_predator];
+        sim.process_life_cycle(&mut rng());
+
+        assert_eq!(sim.world.agents_of(0).count(), 1);
+        assert_eq!(sim.populations[0].num_dead, 1);
+        assert_eq!(sim.populations[1].num_dead, 1);
+
+        // Predators went extinct, so they're back with a fresh population
+        let predators: Vec<_> = sim.world.agents_of(1).collect();
+
+        assert_eq!(predators.len(), sim.config.species[1].count);
+        assert!(predators.iter().all(|agent| agent.alive));
+        assert!(predators.iter().all(|agent| agent.lineage.is_some()));
+    }
+
+    #[test]
+    fn continuous_samples() {
+        let mut rng = rng();
+        let mut sim = continuous();
+
+        sim.world.agents = vec![
+            agent_at(&sim, 0, 0.5, 0.5),
+            Agent {
+                ticks_alive: 100,
+                ..agent_at(&sim, 0, 0.6, 0.5)
+            },
+            agent_at(&sim, 1, 0.1, 0.1),
+        ];
+
+        for tick in 1..=9 {
+            assert!(sim.step(&mut rng).is_none(), "tick {tick}");
+        }
+
+        let stats = sim.step(&mut rng).unwrap();
+
+        assert_eq!(stats.generation, 0);
+        assert_eq!(stats.species(PREY_SPECIES).unwrap().num_dead, 1);
+        assert_eq!(sim.populations[0].num_dead, 0);
+
+        for _ in 0..9 {
+            sim.step(&mut rng);
+        }
+
+        let stats = sim.step(&mut rng).unwrap();
+
+        assert_eq!(stats.generation, 1);
+        assert_eq!(stats.species(PREY_SPECIES).unwrap().num_dead, 0);
+    }
+
+    #[test]
+    fn continuous_survival() {
+        let mut sim = continuous();
+        sim.config.species[0].fitness = Arc::new(Survival);
+
+        sim.world.agents = vec![
+            Agent {
+                ticks_alive: 25,
+                ..agent_at(&sim, 0, 0.5, 0.5)
+            },
+            Agent {
+                ticks_alive: 50,
+                ..agent_at(&sim, 0, 0.6, 0.5)
+            },
+        ];
+
+        // Lifetimes are measured against `max_age` rather than the length
+        // of a generation
+        let stats = sim.current_statistics();
+        let prey = stats.species(PREY_SPECIES).unwrap();
+
+        assert_eq!(prey.ga.min_fitness, 0.25);
+        assert_eq!(prey.ga.max_fitness, 0.5);
+        assert_eq!(sim.world.agents[1].performance(100).survival, 0.5);
+    }
+
+    #[test]
+    fn perceived_inputs_fit_brains() {
+        let mut rng = rng();
+
+        let channels = vec![
+            EyeChannel::new(ChannelKind::Food),
+            EyeChannel::new(ChannelKind::Prey),
+            EyeChannel::new(ChannelKind::Predators),
+            EyeChannel {
+                cells: 3,
+                ..EyeChannel::new(ChannelKind::SameSpecies)
+            },
+            EyeChannel::new(ChannelKind::Obstacles),
+        ];
+
+        let modes = [
+            EyeMode::Summed,
+            EyeMode::Nearest {
+                occlusion: true,
+                object_types: false,
+            },
+            EyeMode::Nearest {
+                occlusion: false,
+                object_types: true,
+            },
+        ];
+
+        for eye_mode in modes {
+            let mut config = food_web();
+
+            for species in &mut config.species {
+                species.channels = channels.clone();
+                species.eye_mode = eye_mode;
+            }
+
+            let mut sim = Simulation::random_with_config(&mut rng, config);
+
+            for _ in 0..10 {
+                sim.step(&mut rng);
+            }
+
+            for idx in 0..sim.world.agents.len() {
+                let cfg = &sim.config.species[sim.world.agents[idx].species];
+                let (inputs, _) = sim.perceive(&mut rng, idx);
+
+                assert_eq!(inputs.len(), cfg.input_neurons(), "{eye_mode:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn same_species_channel() {
+        let config = SimulationConfig {
+            species: vec![SpeciesConfig {
+                channels: vec![
+                    EyeChannel::new(ChannelKind::SameSpecies),
+                    EyeChannel {
+                        gain: 2.0,
+                        ..EyeChannel::new(ChannelKind::SameSpecies)
+                    },
+                ],
+                ..SpeciesConfig::prey()
+            }],
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng(), config);
+        let cells = sim.config.species[0].channels[0].cells;
+
+        // Facing up, i.e. towards the other one
+        let facing_up = |agent: Agent| Agent {
+            rotation: na::Rotation2::new(0.0),
+            ..agent
+        };
+
+        sim.world.agents = vec![facing_up(agent_at(&sim, 0, 0.5, 0.5))];
+        sim.world.rebuild_index();
+
+        let (alone, _) = sim.perceive(&mut rng(), 0);
+        assert!(alone.iter().all(|input| *input == 0.0), "{alone:?}");
+
+        sim.world.agents.push(agent_at(&sim, 0, 0.5, 0.6));
+        sim.world.rebuild_index();
+
+        let (inputs, _) = sim.perceive(&mut rng(), 0);
+        let (plain, amplified) = inputs.split_at(cells);
+
+        assert!(plain.iter().any(|input| *input > 0.0), "{plain:?}");
+
+        for (plain, amplified) in plain.iter().zip(amplified) {
+            assert!((amplified - 2.0 * plain).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn senses() {
+        let mut rng = rng();
+
+        let all = Senses {
+            energy: true,
+            hunger: true,
+            speed: true,
+            age: true,
+            bias: true,
+            noise: true,
+        };
+
+        let only = [
+            Senses {
+                energy: true,
+                ..Default::default()
+            },
+            Senses {
+                hunger: true,
+                ..Default::default()
+            },
+            Senses {
+                speed: true,
+                ..Default::default()
+            },
+            Senses {
+                age: true,
+                ..Default::default()
+            },
+            Senses {
+                bias: true,
+                ..Default::default()
+            },
+            Senses {
+                noise: true,
+                ..Default::default()
+            },
+        ];
+
+        let vision = SpeciesConfig::prey().input_neurons();
+
+        for senses in only {
+            let mut config = SimulationConfig::default();
+            config.species[0].senses = senses;
+
+            let sim = Simulation::random_with_config(&mut rng, config);
+            let (inputs, _) = sim.perceive(&mut rng, 0);
+
+            assert_eq!(senses.inputs(), 1);
+            assert_eq!(inputs.len(), vision + 1, "{senses:?}");
+        }
+
+        let mut config = SimulationConfig::default();
+
+        for species in &mut config.species {
+            species.senses = all;
+            species.metabolism = Some(Metabolism::default());
+            species.body_genes = Some(BodyGenes::default());
+        }
+
+        let mut sim = Simulation::random_with_config(&mut rng, config);
+
+        for _ in 0..100 {
+            sim.step(&mut rng);
+
+            for idx in 0..sim.world.agents.len() {
+                if !sim.world.agents[idx].alive {
+                    continue;
+                }
+
+                let species = &sim.config.species[sim.world.agents[idx].species];
+                let (inputs, _) = sim.perceive(&mut rng, idx);
+
+                assert_eq!(inputs.len(), species.input_neurons());
+
+                let senses = &inputs[inputs.len() - all.inputs()..];
+                let [energy, hunger, speed, age, bias, noise] = senses else {
+                    unreachable!();
+                };
+
+                for sense in [energy, hunger, speed, age] {
+                    assert!((0.0..=1.0).contains(sense), "{senses:?}");
+                }
+
+                assert_eq!(*bias, 1.0);
+                assert!((-1.0..=1.0).contains(noise), "{senses:?}");
+            }
+        }
+
+        let predator = sim
+            .world
+            .agents
+            .iter()
+            .position(|agent| agent.species == 1)
+            .unwrap();
+        let agent = &mut sim.world.agents[predator];
+
+        agent.energy = 0.25;
+        agent.hunger = 0.5;
+        agent.speed = agent.speed_max;
+
+        let (inputs, _) = sim.perceive(&mut rng, predator);
+        let senses = &inputs[inputs.len() - all.inputs()..];
+
+        assert_eq!(senses[..3], [0.25, 0.5, 1.0]);
+        assert_eq!(senses[3], 100.0 / GENERATION_LENGTH as f32);
+    }
+
+    #[test]
+    fn world_covered_by_obstacles() {
+        let mut rng = rng();
+
+        let config = SimulationConfig {
+            obstacles: vec![Obstacle::Rect {
+                min: na::Point2::new(0.0, 0.0),
+                max: na::Point2::new(1.0, 1.0),
+            }],
+            food_spawning: FoodSpawning::Patches {
+                count: 2,
+                radius: 0.1,
+            },
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config);
+        sim.step(&mut rng);
+    }
+
+    #[test]
+    fn bigger_eyes_cost_speed_and_energy() {
+        let mut rng = rng();
+
+        let config = SimulationConfig {
+            species: vec![SpeciesConfig {
+                eats_food: false,
+                eye_genes: Some(EyeGenes::default()),
+                metabolism: Some(Metabolism::default()),
+                ..SpeciesConfig::prey()
+            }],
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config);
+        let small = idle_agent(&sim, 0, &[-100.0, -100.0]);
+        let big = idle_agent(&sim, 0, &[100.0, 100.0]);
+
+        assert!(big.eyes[0].fov_range() > small.eyes[0].fov_range());
+        assert!(big.speed_max < small.speed_max);
+
+        sim.world.agents = vec![small, big];
+
+        for _ in 0..100 {
+            sim.step(&mut rng);
+        }
+
+        let [small, big] = [&sim.world.agents[0], &sim.world.agents[1]];
+
+        assert!(small.alive && big.alive);
+        assert_eq!(small.speed, big.speed);
+        assert!(big.energy < small.energy);
+    }
+
+    #[test]
+    fn max_agent_speed() {
+        let config = SimulationConfig {
+            species: vec![SpeciesConfig {
+                body_genes: Some(BodyGenes::default()),
+                ..SpeciesConfig::prey()
+            }],
+            ..Default::default()
+        };
+
+        let sim = Simulation::random_with_config(&mut rng(), config);
+        let cfg = &sim.config.species[0];
+
+        for speed in [-100.0, 0.0, 100.0] {
+            let agent = idle_agent(&sim, 0, &[0.0, speed, 0.0]);
+            assert!(agent.speed_max <= cfg.max_agent_speed());
+        }
+
+        let fastest = idle_agent(&sim, 0, &[0.0, 100.0, 0.0]);
+
+        assert!(fastest.speed_max > cfg.speed_max);
+        assert!((fastest.speed_max - cfg.max_agent_speed()).abs() < 1e-6);
+        assert_eq!(
+            SpeciesConfig::prey().max_agent_speed(),
+            SpeciesConfig::prey().speed_max
+        );
+    }
 }
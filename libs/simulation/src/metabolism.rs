@@ -0,0 +1,89 @@
+// How animals (or predators) spend and regain energy.
+//
+// Energy is measured in full tanks, i.e. it stays within `0.0..=1.0`;
+// everyone is born with a full tank and dies once it runs dry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Metabolism {
+    // Spent each tick, just for being alive
+    pub basal_cost: f32,
+    // Spent each tick while moving at the species' top speed,
+    // proportionally less (or more) when slower (or faster)
+    pub speed_cost: f32,
+    // Spent each tick while turning as fast as the species can,
+    // proportionally less (or more) when turning slower (or faster)
+    pub turn_cost: f32,
+    // Gained from each food eaten (or prey caught, for predators)
+    pub meal_energy: f32,
+}
+
+impl Default for Metabolism {
+    fn default() -> Self {
+        Self {
+            basal_cost: 0.0002,
+            speed_cost: 0.0006,
+            turn_cost: 0.0002,
+            meal_energy: 0.3,
+        }
+    }
+}
+
+impl Metabolism {
+    pub(crate) fn normalized(self) -> Self {
+        Self {
+            basal_cost: self.basal_cost.max(0.0),
+            speed_cost: self.speed_cost.max(0.0),
+            turn_cost: self.turn_cost.max(0.0),
+            meal_energy: self.meal_energy.max(0.0),
+        }
+    }
+
+    // `speed` and `turn` are relative to the species' top ones; they're not
+    // capped, since evolved bodies may exceed those (see `BodyGenes`)
+    pub(crate) fn cost(&self, speed: f32, turn: f32) -> f32 {
+        self.basal_cost + self.speed_cost * speed.abs() + self.turn_cost * turn.abs()
+    }
+
+    // `portion` scales the meal, e.g. by food's nutrition
+    pub(crate) fn eat(&self, energy: f32, portion: f32) -> f32 {
+        (energy + self.meal_energy * portion).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METABOLISM: Metabolism = Metabolism {
+        basal_cost: 0.5,
+        speed_cost: 2.0,
+        turn_cost: 1.0,
+        meal_energy: 0.25,
+    };
+
+    #[test]
+    fn cost() {
+        assert_eq!(METABOLISM.cost(0.0, 0.0), 0.5);
+        assert_eq!(METABOLISM.cost(0.5, 0.0), 1.5);
+        assert_eq!(METABOLISM.cost(0.0, -0.5), 1.0);
+        assert_eq!(METABOLISM.cost(-1.0, 1.0), 3.5);
+    }
+
+    #[test]
+    fn eat() {
+        assert_eq!(METABOLISM.eat(0.25, 1.0), 0.5);
+        assert_eq!(METABOLISM.eat(0.25, 2.0), 0.75);
+        assert_eq!(METABOLISM.eat(0.9, 1.0), 1.0);
+    }
+
+    #[test]
+    fn cost_beyond_top_speed() {
+        let metabolism = Metabolism::default();
+
+        let top = metabolism.cost(1.0, 1.0);
+        let faster = metabolism.cost(1.5, 1.0);
+        let sharper = metabolism.cost(1.0, 1.5);
+
+        assert!((faster - top - metabolism.speed_cost * 0.5).abs() < 1e-7);
+        assert!((sharper - top - metabolism.turn_cost * 0.5).abs() < 1e-7);
+    }
+}
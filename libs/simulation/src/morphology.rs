@@ -0,0 +1,257 @@
+use crate::*;
+
+// Heritable traits other than the brain are encoded as extra genes,
+// appended to the chromosome after brain weights; each gene gets expressed
+// as a value within `0.0..=1.0`, which is then mapped onto the trait's
+// bounds
+
+// Lets eye shape evolve - see `SpeciesConfig::eye_genes`.
+//
+// Bigger eyes (ones seeing a larger area) come at a price: up to
+// `energy_cost` more energy spent each tick and up to `speed_cost` of the
+// top speed lost, for the biggest eye possible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EyeGenes {
+    pub min_fov_angle: f32,
+    pub max_fov_angle: f32,
+    // Bounds of the factor every eye channel's `EyeChannel::fov_range`
+    // gets scaled by
+    pub min_range_scale: f32,
+    pub max_range_scale: f32,
+    pub energy_cost: f32,
+    pub speed_cost: f32,
+}
+
+impl Default for EyeGenes {
+    fn default() -> Self {
+        Self {
+            min_fov_angle: FRAC_PI_4,
+            max_fov_angle: TAU,
+            min_range_scale: 0.5,
+            max_range_scale: 2.0,
+            energy_cost: 0.0004,
+            speed_cost: 0.3,
+        }
+    }
+}
+
+impl EyeGenes {
+    // How many genes eye shape takes
+    pub(crate) const GENES: usize = 2;
+
+    pub(crate) fn normalized(self) -> Self {
+        let min_fov_angle = self.min_fov_angle.clamp(0.01, TAU);
+        let min_range_scale = self.min_range_scale.max(0.01);
+
+        Self {
+            min_fov_angle,
+            max_fov_angle: self.max_fov_angle.clamp(min_fov_angle, TAU),
+            min_range_scale,
+            max_range_scale: self.max_range_scale.max(min_range_scale),
+            energy_cost: self.energy_cost.max(0.0),
+            speed_cost: self.speed_cost.clamp(0.0, 1.0),
+        }
+    }
+
+    pub(crate) fn express(&self, genes: &[f32]) -> EyeShape {
+        let fov_angle = lerp(self.min_fov_angle, self.max_fov_angle, express(genes[0]));
+        let range_scale = lerp(
+            self.min_range_scale,
+            self.max_range_scale,
+            express(genes[1]),
+        );
+
+        // Area of the view, relative to the biggest one possible
+        let size =
+            (fov_angle * range_scale.powi(2)) / (self.max_fov_angle * self.max_range_scale.powi(2));
+
+        EyeShape {
+            fov_angle,
+            range_scale,
+            energy_cost: self.energy_cost * size,
+            speed_cost: self.speed_cost * size,
+        }
+    }
+}
+
+// What an agent's eyes look like, see `EyeGenes`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EyeShape {
+    pub fov_angle: f32,
+    pub range_scale: f32,
+    // Extra energy spent each tick
+    pub energy_cost: f32,
+    // Fraction of top speed lost
+    pub speed_cost: f32,
+}
+
+impl EyeShape {
+    // Eyes of species with no `EyeGenes`
+    pub(crate) fn fixed(fov_angle: f32) -> Self {
+        Self {
+            fov_angle,
+            range_scale: 1.0,
+            energy_cost: 0.0,
+            speed_cost: 0.0,
+        }
+    }
+}
+
+// Lets body shape evolve - see `SpeciesConfig::body_genes`.
+//
+// Each trait scales its species' counterpart: `size` scales `body_radius`
+// and `reach` (bigger bodies reach further, but also get caught from
+// further away), `speed` scales `speed_max` and `agility` scales
+// `rotation_accel`. Heavy, fast and agile bodies don't come for free: up to
+// `energy_cost` more energy is spent each tick, for the biggest, fastest and
+// most agile body possible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BodyGenes {
+    pub min_size: f32,
+    pub max_size: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub min_agility: f32,
+    pub max_agility: f32,
+    pub energy_cost: f32,
+}
+
+impl Default for BodyGenes {
+    fn default() -> Self {
+        Self {
+            min_size: 0.5,
+            max_size: 2.0,
+            min_speed: 0.5,
+            max_speed: 1.5,
+            min_agility: 0.5,
+            max_agility: 1.5,
+            energy_cost: 0.0008,
+        }
+    }
+}
+
+impl BodyGenes {
+    // How many genes body shape takes
+    pub(crate) const GENES: usize = 3;
+
+    pub(crate) fn normalized(self) -> Self {
+        let min_size = self.min_size.max(0.01);
+        let min_speed = self.min_speed.max(0.0);
+        let min_agility = self.min_agility.max(0.0);
+
+        Self {
+            min_size,
+            max_size: self.max_size.max(min_size),
+            min_speed,
+            max_speed: self.max_speed.max(min_speed).max(f32::EPSILON),
+            min_agility,
+            max_agility: self.max_agility.max(min_agility).max(f32::EPSILON),
+            energy_cost: self.energy_cost.max(0.0),
+        }
+    }
+
+    pub(crate) fn express(&self, genes: &[f32]) -> BodyShape {
+        let size = lerp(self.min_size, self.max_size, express(genes[0]));
+        let speed = lerp(self.min_speed, self.max_speed, express(genes[1]));
+        let agility = lerp(self.min_agility, self.max_agility, express(genes[2]));
+
+        // Heavier bodies need more energy to move fast and turn sharply
+        let upkeep = (size / self.max_size)
+            * ((speed / self.max_speed).powi(2) + (agility / self.max_agility).powi(2))
+            / 2.0;
+
+        BodyShape {
+            size,
+            speed,
+            agility,
+            energy_cost: self.energy_cost * upkeep,
+        }
+    }
+}
+
+// What an agent's body looks like, see `BodyGenes`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BodyShape {
+    pub size: f32,
+    pub speed: f32,
+    pub agility: f32,
+    // Extra energy spent each tick
+    pub energy_cost: f32,
+}
+
+impl BodyShape {
+    // Bodies of species with no `BodyGenes`
+    pub(crate) fn fixed() -> Self {
+        Self {
+            size: 1.0,
+            speed: 1.0,
+            agility: 1.0,
+            energy_cost: 0.0,
+        }
+    }
+}
+
+// Draws trait genes the same way `nn::Network::random()` draws weights
+pub(crate) fn random_genes(rng: &mut dyn RngCore, count: usize) -> Vec<f32> {
+    (0..count).map(|_| rng.gen_range(-1.0..=1.0)).collect()
+}
+
+// Maps a gene onto `0.0..=1.0`
+fn express(gene: f32) -> f32 {
+    (gene.tanh() + 1.0) / 2.0
+}
+
+fn lerp(min: f32, max: f32, t: f32) -> f32 {
+    min + (max - min) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eye_shape() {
+        let genes = EyeGenes::default();
+
+        let small = genes.express(&[-100.0, -100.0]);
+        let big = genes.express(&[100.0, 100.0]);
+        let medium = genes.express(&[0.0, 0.0]);
+
+        assert!((small.fov_angle - genes.min_fov_angle).abs() < 1e-5);
+        assert!((small.range_scale - genes.min_range_scale).abs() < 1e-5);
+        assert!((big.fov_angle - genes.max_fov_angle).abs() < 1e-5);
+        assert!((big.range_scale - genes.max_range_scale).abs() < 1e-5);
+        assert!((big.energy_cost - genes.energy_cost).abs() < 1e-6);
+        assert!((big.speed_cost - genes.speed_cost).abs() < 1e-5);
+
+        assert!(small.speed_cost < medium.speed_cost);
+        assert!(medium.speed_cost < big.speed_cost);
+        assert!(small.energy_cost < medium.energy_cost);
+        assert!(medium.energy_cost < big.energy_cost);
+    }
+
+    #[test]
+    fn body_shape() {
+        let genes = BodyGenes::default();
+
+        let small = genes.express(&[-100.0, -100.0, -100.0]);
+        let big = genes.express(&[100.0, 100.0, 100.0]);
+        let fast = genes.express(&[0.0, 100.0, -100.0]);
+        let agile = genes.express(&[0.0, -100.0, 100.0]);
+        let heavy = genes.express(&[100.0, 0.0, 0.0]);
+        let light = genes.express(&[-100.0, 0.0, 0.0]);
+
+        assert!((small.size - genes.min_size).abs() < 1e-5);
+        assert!((small.speed - genes.min_speed).abs() < 1e-5);
+        assert!((small.agility - genes.min_agility).abs() < 1e-5);
+        assert!((big.size - genes.max_size).abs() < 1e-5);
+        assert!((big.speed - genes.max_speed).abs() < 1e-5);
+        assert!((big.agility - genes.max_agility).abs() < 1e-5);
+        assert!((big.energy_cost - genes.energy_cost).abs() < 1e-6);
+
+        assert!((fast.energy_cost - agile.energy_cost).abs() < 1e-6);
+        assert!(small.energy_cost < fast.energy_cost);
+        assert!(fast.energy_cost < big.energy_cost);
+        assert!(light.energy_cost < heavy.energy_cost);
+    }
+}
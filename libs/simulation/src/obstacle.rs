@@ -0,0 +1,352 @@
+use crate::*;
+
+// How many random positions are tried before giving up on finding a free
+// one, e.g. when obstacles cover the whole world
+pub(crate) const PLACEMENT_ATTEMPTS: usize = 1000;
+
+// What happens to animals (and predators) running into an obstacle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ObstacleResponse {
+    // They keep their heading and slide along the obstacle
+    #[default]
+    Slide,
+    // They bounce off of it, as off a mirror
+    Bounce,
+}
+
+// Something that blocks both movement and sight.
+//
+// Obstacles live in the unit square's coordinates and, unlike everything
+// else, don't wrap around the edges of `Topology::Torus`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Obstacle {
+    Circle {
+        center: na::Point2<f32>,
+        radius: f32,
+    },
+    // Axis-aligned box
+    Rect {
+        min: na::Point2<f32>,
+        max: na::Point2<f32>,
+    },
+    // Infinitely thin wall
+    Segment {
+        from: na::Point2<f32>,
+        to: na::Point2<f32>,
+    },
+}
+
+impl Obstacle {
+    pub(crate) fn normalized(self) -> Self {
+        match self {
+            Self::Circle { center, radius } => Self::Circle {
+                center,
+                radius: radius.abs(),
+            },
+            Self::Rect { min, max } => Self::Rect {
+                min: min.inf(&max),
+                max: min.sup(&max),
+            },
+            Self::Segment { .. } => self,
+        }
+    }
+
+    pub fn contains(&self, point: na::Point2<f32>) -> bool {
+        self.separation(point).0 < 0.0
+    }
+
+    // Signed distance from `point` to the obstacle's surface (negative when
+    // it's inside) and the surface's normal there, pointing towards `point`
+    pub fn separation(&self, point: na::Point2<f32>) -> (f32, na::Vector2<f32>) {
+        match *self {
+            Self::Circle { center, radius } => {
+                let (distance, normal) = direction(point - center);
+                (distance - radius, normal)
+            }
+
+            Self::Rect { min, max } => {
+                let closest =
+                    na::Point2::new(point.x.clamp(min.x, max.x), point.y.clamp(min.y, max.y));
+
+                if closest != point {
+                    return direction(point - closest);
+                }
+
+                // We're inside, so let's find the closest face
+                [
+                    (point.x - min.x, -na::Vector2::x()),
+                    (max.x - point.x, na::Vector2::x()),
+                    (point.y - min.y, -na::Vector2::y()),
+                    (max.y - point.y, na::Vector2::y()),
+                ]
+                .into_iter()
+                .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(distance, normal)| (-distance, normal))
+                .unwrap()
+            }
+
+            Self::Segment { from, to } => {
+                let along = to - from;
+                let t = ((point - from).dot(&along) / along.norm_squared().max(f32::EPSILON))
+                    .clamp(0.0, 1.0);
+
+                direction(point - (from + along * t))
+            }
+        }
+    }
+
+    // Moves `position` so that a body of given radius centered there
+    // doesn't overlap the obstacle; returns the surface's normal if there
+    // was anything to do
+    pub fn push_out(
+        &self,
+        position: &mut na::Point2<f32>,
+        radius: f32,
+    ) -> Option<na::Vector2<f32>> {
+        let (distance, normal) = self.separation(*position);
+
+        if distance >= radius {
+            return None;
+        }
+
+        *position += normal * (radius - distance);
+        Some(normal)
+    }
+
+    // Distance along the ray (`direction` must be normalized) at which it
+    // first hits the obstacle, if it does so before `max_distance`
+    pub fn raycast(
+        &self,
+        origin: na::Point2<f32>,
+        direction: na::Vector2<f32>,
+        max_distance: f32,
+    ) -> Option<f32> {
+        let hit = match *self {
+            Self::Circle { center, radius } => {
+                let offset = origin - center;
+                let b = offset.dot(&direction);
+                let c = offset.norm_squared() - radius * radius;
+
+                if c <= 0.0 {
+                    return Some(0.0);
+                }
+
+                let discriminant = b * b - c;
+
+                if discriminant < 0.0 {
+                    return None;
+                }
+
+                -b - discriminant.sqrt()
+            }
+
+            Self::Rect { min, max } => {
+                let mut near = f32::NEG_INFINITY;
+                let mut far = f32::INFINITY;
+
+                for axis in 0..2 {
+                    if direction[axis] == 0.0 {
+                        if !(min[axis]..=max[axis]).contains(&origin[axis]) {
+                            return None;
+                        }
+                    } else {
+                        let a = (min[axis] - origin[axis]) / direction[axis];
+                        let b = (max[axis] - origin[axis]) / direction[axis];
+
+                        near = near.max(a.min(b));
+                        far = far.min(a.max(b));
+                    }
+                }
+
+                if near > far || far < 0.0 {
+                    return None;
+                }
+
+                near.max(0.0)
+            }
+
+            Self::Segment { from, to } => {
+                let along = to - from;
+                let denominator = direction.perp(&along);
+
+                if denominator == 0.0 {
+                    return None;
+                }
+
+                let offset = from - origin;
+                let t = offset.perp(&along) / denominator;
+                let u = offset.perp(&direction) / denominator;
+
+                if !(0.0..=1.0).contains(&u) {
+                    return None;
+                }
+
+                t
+            }
+        };
+
+        (0.0..max_distance).contains(&hit).then_some(hit)
+    }
+
+    // Whether the obstacle stands in the way between `from` and `to`
+    pub fn blocks(&self, from: na::Point2<f32>, to: na::Point2<f32>) -> bool {
+        let (distance, direction) = direction(to - from);
+        self.raycast(from, direction, distance).is_some()
+    }
+}
+
+// Length and direction of given vector - with some direction, even for a
+// zero-length vector
+fn direction(vec: na::Vector2<f32>) -> (f32, na::Vector2<f32>) {
+    let length = vec.norm();
+
+    if length > 0.0 {
+        (length, vec / length)
+    } else {
+        (0.0, na::Vector2::y())
+    }
+}
+
+// Pushes whatever's at `position` out of obstacles it's run into, possibly
+// bouncing it off of them
+pub(crate) fn collide_with_obstacles(
+    obstacles: &[Obstacle],
+    response: ObstacleResponse,
+    radius: f32,
+    position: &mut na::Point2<f32>,
+    rotation: &mut na::Rotation2<f32>,
+) {
+    for obstacle in obstacles {
+        let Some(normal) = obstacle.push_out(position, radius) else {
+            continue;
+        };
+
+        if response == ObstacleResponse::Bounce {
+            bounce(rotation, normal);
+        }
+    }
+}
+
+// Whether `to` can be seen from `from`
+pub(crate) fn in_sight(obstacles: &[Obstacle], from: na::Point2<f32>, to: na::Point2<f32>) -> bool {
+    !obstacles.iter().any(|obstacle| obstacle.blocks(from, to))
+}
+
+// Whether something can be placed at `position` - i.e. whether it's within
+// the world and not stuck in any obstacle
+pub(crate) fn is_free(
+    obstacles: &[Obstacle],
+    topology: Topology,
+    position: na::Point2<f32>,
+) -> bool {
+    topology.contains(position) && !obstacles.iter().any(|obstacle| obstacle.contains(position))
+}
+
+// Falls back to a position that isn't free after `PLACEMENT_ATTEMPTS`
+pub(crate) fn random_free_position(
+    rng: &mut dyn RngCore,
+    obstacles: &[Obstacle],
+    topology: Topology,
+) -> na::Point2<f32> {
+    let mut position = topology.random_position(rng);
+
+    for _ in 1..PLACEMENT_ATTEMPTS {
+        if is_free(obstacles, topology, position) {
+            break;
+        }
+
+        position = topology.random_position(rng);
+    }
+
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn circle() -> Obstacle {
+        Obstacle::Circle {
+            center: na::Point2::new(0.5, 0.5),
+            radius: 0.1,
+        }
+    }
+
+    fn rect() -> Obstacle {
+        Obstacle::Rect {
+            min: na::Point2::new(0.4, 0.4),
+            max: na::Point2::new(0.6, 0.6),
+        }
+    }
+
+    fn segment() -> Obstacle {
+        Obstacle::Segment {
+            from: na::Point2::new(0.5, 0.4),
+            to: na::Point2::new(0.5, 0.6),
+        }
+    }
+
+    #[test]
+    fn gives_up_on_finding_free_position() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let everywhere = Obstacle::Rect {
+            min: na::Point2::new(0.0, 0.0),
+            max: na::Point2::new(1.0, 1.0),
+        };
+
+        let position = random_free_position(&mut rng, &[everywhere], Topology::Torus);
+        assert!(!is_free(&[everywhere], Topology::Torus, position));
+
+        let position = random_free_position(&mut rng, &[rect()], Topology::Torus);
+        assert!(is_free(&[rect()], Topology::Torus, position));
+    }
+
+    #[test]
+    fn blocks_sight() {
+        let from = na::Point2::new(0.2, 0.5);
+        let behind = na::Point2::new(0.8, 0.5);
+        let aside = na::Point2::new(0.8, 0.9);
+
+        for obstacle in [circle(), rect(), segment()] {
+            assert!(obstacle.blocks(from, behind), "{obstacle:?}");
+            assert!(!obstacle.blocks(from, aside), "{obstacle:?}");
+            assert!(
+                !obstacle.blocks(from, na::Point2::new(0.3, 0.5)),
+                "{obstacle:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn raycast() {
+        let origin = na::Point2::new(0.2, 0.5);
+        let direction = na::Vector2::x();
+
+        let hits =
+            [circle(), rect(), segment()].map(|obstacle| obstacle.raycast(origin, direction, 1.0));
+
+        for (hit, expected) in hits.into_iter().zip([0.2, 0.2, 0.3]) {
+            assert!((hit.unwrap() - expected).abs() < 1e-5, "{hit:?}");
+        }
+
+        assert_eq!(circle().raycast(origin, direction, 0.1), None);
+    }
+
+    #[test]
+    fn pushes_out() {
+        for obstacle in [circle(), rect(), segment()] {
+            let mut position = na::Point2::new(0.495, 0.52);
+
+            assert!(obstacle.push_out(&mut position, 0.01).is_some());
+            assert!(
+                obstacle.separation(position).0 >= 0.01 - 1e-5,
+                "{obstacle:?}"
+            );
+
+            let mut far_away = na::Point2::new(0.9, 0.9);
+            assert!(obstacle.push_out(&mut far_away, 0.01).is_none());
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// What brains perceive on top of what their eyes see - each of these takes
+// a single input, in order they're listed here
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Senses {
+    // Current energy level, see `Metabolism`
+    pub energy: bool,
+    // How close to starving (`0.0..=1.0`) one is, see `Hunger`
+    pub hunger: bool,
+    // Current speed, relative to the top one
+    pub speed: bool,
+    // Fraction of the generation (or, in `EvolutionMode::Continuous`, of
+    // one's lifespan) that has passed
+    pub age: bool,
+    // Always `1.0`
+    pub bias: bool,
+    // Random, within `-1.0..=1.0`
+    pub noise: bool,
+}
+
+impl Senses {
+    // Number of brain inputs these senses take
+    pub(crate) fn inputs(&self) -> usize {
+        [
+            self.energy,
+            self.hunger,
+            self.speed,
+            self.age,
+            self.bias,
+            self.noise,
+        ]
+        .into_iter()
+        .filter(|sense| *sense)
+        .count()
+    }
+}
@@ -0,0 +1,150 @@
+use crate::*;
+
+// Largest number of cells along each side `fit()` goes for
+const MAX_SIZE: usize = 64;
+
+// Uniform grid over the (wrapping) unit square, so that finding objects
+// close to some point doesn't require checking all of them.
+//
+// The grid stores indices into some other collection (e.g. `World::foods`),
+// which have to be kept in sync by the caller - either by calling
+// `rebuild()` or by reporting moved objects through `relocate()`.
+#[derive(Clone, Debug)]
+pub struct SpatialGrid {
+    size: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    // `size` is the number of cells along each side
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+
+        Self {
+            size,
+            cells: vec![Vec::new(); size * size],
+        }
+    }
+
+    // Picks a size leaving `objects_per_cell` objects per cell on average,
+    // so that queries don't waste time on lots of empty cells (or on lots
+    // of objects in just a few cells)
+    pub fn fit(&mut self, objects: usize, objects_per_cell: f32) {
+        let size = ((objects as f32 / objects_per_cell).sqrt().ceil() as usize).clamp(1, MAX_SIZE);
+
+        if size != self.size {
+            *self = Self::new(size);
+        }
+    }
+
+    // Takes index and position of each object; objects that are left out
+    // (e.g. dead animals) won't be found
+    pub fn rebuild(&mut self, objects: impl IntoIterator<Item = (usize, na::Point2<f32>)>) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+
+        for (idx, position) in objects {
+            let cell = self.cell(position);
+            self.cells[cell].push(idx);
+        }
+    }
+
+    pub fn relocate(&mut self, idx: usize, from: na::Point2<f32>, to: na::Point2<f32>) {
+        let (from, to) = (self.cell(from), self.cell(to));
+
+        if from != to {
+            self.cells[from].retain(|&other| other != idx);
+            self.cells[to].push(idx);
+        }
+    }
+
+    // Returns indices of objects that might be within `radius` of given
+    // position (and those of some that are further away - it's up to the
+    // caller to check actual distances)
+    pub fn query(
+        &self,
+        position: na::Point2<f32>,
+        radius: f32,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let xs = self.span(position.x, radius);
+
+        self.span(position.y, radius)
+            .flat_map(move |y| xs.clone().map(move |x| y * self.size + x))
+            .flat_map(move |cell| self.cells[cell].iter().copied())
+    }
+
+    fn cell(&self, position: na::Point2<f32>) -> usize {
+        let coord = |value: f32| {
+            ((value * self.size as f32).floor() as isize).rem_euclid(self.size as isize) as usize
+        };
+
+        coord(position.y) * self.size + coord(position.x)
+    }
+
+    // Cells overlapping `coord - radius..=coord + radius`, wrapped around
+    // the edges, each one at most once
+    fn span(&self, coord: f32, radius: f32) -> impl Iterator<Item = usize> + Clone + use<> {
+        let size = self.size as isize;
+        let from = ((coord - radius) * size as f32).floor() as isize;
+        let to = ((coord + radius) * size as f32).floor() as isize;
+
+        let (from, to) = if to - from + 1 >= size {
+            (0, size - 1)
+        } else {
+            (from, to)
+        };
+
+        (from..=to).map(move |cell| cell.rem_euclid(size) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(grid: &SpatialGrid, x: f32, y: f32, radius: f32) -> Vec<usize> {
+        let mut found: Vec<_> = grid.query(na::Point2::new(x, y), radius).collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn finds_nearby_objects() {
+        let mut grid = SpatialGrid::new(10);
+
+        grid.rebuild([
+            (0, na::Point2::new(0.51, 0.51)),
+            (1, na::Point2::new(0.55, 0.45)),
+            (2, na::Point2::new(0.9, 0.1)),
+        ]);
+
+        assert_eq!(query(&grid, 0.5, 0.5, 0.05), vec![0, 1]);
+        assert_eq!(query(&grid, 0.9, 0.1, 0.01), vec![2]);
+        assert_eq!(query(&grid, 0.5, 0.5, 10.0), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn wraps_around_edges() {
+        let mut grid = SpatialGrid::new(10);
+
+        grid.rebuild([
+            (0, na::Point2::new(0.99, 0.01)),
+            (1, na::Point2::new(0.5, 0.5)),
+        ]);
+
+        assert_eq!(query(&grid, 0.01, 0.99, 0.05), vec![0]);
+    }
+
+    #[test]
+    fn relocates_objects() {
+        let mut grid = SpatialGrid::new(10);
+        let (from, to) = (na::Point2::new(0.15, 0.15), na::Point2::new(0.85, 0.85));
+
+        grid.rebuild([(0, from)]);
+        grid.relocate(0, from, to);
+
+        assert_eq!(query(&grid, 0.15, 0.15, 0.01), Vec::<usize>::new());
+        assert_eq!(query(&grid, 0.85, 0.85, 0.01), vec![0]);
+    }
+}
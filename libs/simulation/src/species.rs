@@ -0,0 +1,258 @@
+use crate::*;
+
+const PREY_COLOR: Rgba = Rgba {
+    r: 80,
+    g: 170,
+    b: 255,
+    a: 255,
+};
+
+const PREDATOR_COLOR: Rgba = Rgba {
+    r: 255,
+    g: 90,
+    b: 90,
+    a: 255,
+};
+
+// Everything that makes a species what it is - its body, senses, diet and
+// what it's selected for.
+//
+// Species refer to each other (e.g. in `hunts`) by their index within
+// `SimulationConfig::species`.
+#[derive(Clone, Debug)]
+pub struct SpeciesConfig {
+    // Identifies the species in genomes, pedigrees, statistics etc.
+    pub name: String,
+    pub count: usize,
+    // Largest population the species may grow to, see
+    // `PopulationDynamics::Resources` and `EvolutionMode::Continuous`
+    pub max_count: usize,
+    pub color: Rgba,
+    pub hidden_neurons: usize,
+    // What (and how) it sees, in order its brain gets it, see `EyeChannel`
+    pub channels: Vec<EyeChannel>,
+    pub eye_mode: EyeMode,
+    pub fov_angle: f32,
+    // Lets each agent's FOV angle and range evolve instead of being
+    // `fov_angle` and `EyeChannel::fov_range`, see `EyeGenes`
+    pub eye_genes: Option<EyeGenes>,
+    // Lets each agent's size, top speed and agility evolve around the
+    // ones configured here, see `BodyGenes`
+    pub body_genes: Option<BodyGenes>,
+    pub speed_min: f32,
+    pub speed_max: f32,
+    // Speed everyone's born with
+    pub initial_speed: f32,
+    // Largest change of speed (and rotation) the brain can make per tick
+    pub speed_accel: f32,
+    pub rotation_accel: f32,
+    pub eats_food: bool,
+    // Species it catches
+    pub hunts: Vec<usize>,
+    // How close it has to get to food (or someone it hunts) to eat it
+    pub reach: f32,
+    // How far from obstacles it's kept
+    pub body_radius: f32,
+    // Satiation gained from each catch
+    pub kill_reward: f32,
+    // What the species is selected for, see `FitnessFunction`
+    pub fitness: Arc<dyn FitnessFunction>,
+    // How the species spends energy; `None` makes moving free and lets it
+    // live on an empty stomach
+    pub metabolism: Option<Metabolism>,
+    // How long it lasts without catching anyone; `None` lets it live
+    // without hunting
+    pub hunger: Option<Hunger>,
+    // What its brain gets to know about itself, see `Senses`
+    pub senses: Senses,
+    // How much the species is rewarded for behaving differently from
+    // others rather than for its fitness, see `ga::WeightedObjective`; zero
+    // disables novelty search.
+    //
+    // Only applies to `EvolutionMode::Generational` - in the continuous
+    // mode agents breed once they've got enough energy, regardless of
+    // their fitness.
+    pub novelty_weight: f32,
+    pub population_dynamics: PopulationDynamics,
+}
+
+impl SpeciesConfig {
+    // Eats food and runs away from predators
+    pub fn prey() -> Self {
+        Self {
+            name: PREY_SPECIES.into(),
+            count: PREY_COUNT,
+            max_count: PREY_COUNT * 4,
+            color: PREY_COLOR,
+            hidden_neurons: DEFAULT_NEURONS,
+            channels: vec![
+                EyeChannel::new(ChannelKind::Food),
+                // Predators look way more alarming than food
+                EyeChannel {
+                    gain: 2.5,
+                    ..EyeChannel::new(ChannelKind::Predators)
+                },
+            ],
+            eye_mode: EyeMode::Summed,
+            fov_angle: DEFAULT_FOV_ANGLE,
+            eye_genes: None,
+            body_genes: None,
+            speed_min: 0.0007,
+            speed_max: 0.0035,
+            initial_speed: 0.0014,
+            speed_accel: 0.2,
+            rotation_accel: FRAC_PI_2 / 3.0,
+            eats_food: true,
+            hunts: Vec::new(),
+            reach: 0.01,
+            body_radius: 0.005,
+            kill_reward: 0.0,
+            fitness: default_fitness_function(),
+            metabolism: None,
+            hunger: None,
+            senses: Senses::default(),
+            novelty_weight: 0.0,
+            population_dynamics: PopulationDynamics::Fixed,
+        }
+    }
+
+    // Hunts species with given index
+    pub fn predator(prey: usize) -> Self {
+        Self {
+            name: PREDATOR_SPECIES.into(),
+            count: PREDATOR_COUNT,
+            max_count: PREDATOR_COUNT * 4,
+            color: PREDATOR_COLOR,
+            channels: vec![EyeChannel::new(ChannelKind::Prey)],
+            speed_min: 0.0006,
+            speed_max: 0.0032,
+            initial_speed: 0.0011,
+            speed_accel: 0.25,
+            rotation_accel: FRAC_PI_2 / 2.5,
+            eats_food: false,
+            hunts: vec![prey],
+            reach: 0.012,
+            kill_reward: 3.0,
+            hunger: Some(Hunger::default()),
+            ..Self::prey()
+        }
+    }
+
+    // Makes the species proportionally faster (or slower)
+    pub fn with_speed_multiplier(mut self, multiplier: f32) -> Self {
+        let multiplier = multiplier.max(0.01);
+
+        self.speed_min *= multiplier;
+        self.speed_max *= multiplier;
+        self.initial_speed *= multiplier;
+        self
+    }
+
+    // Makes every eye channel this precise
+    pub fn with_photoreceptors(mut self, cells: usize) -> Self {
+        for channel in &mut self.channels {
+            channel.cells = cells;
+        }
+
+        self
+    }
+
+    pub(crate) fn normalized(mut self, num_species: usize) -> Self {
+        self.count = self.count.max(1);
+        self.max_count = self.max_count.max(1);
+        self.hidden_neurons = self.hidden_neurons.max(1);
+        self.channels = self
+            .channels
+            .into_iter()
+            .map(EyeChannel::normalized)
+            .collect();
+        self.fov_angle = self.fov_angle.clamp(0.01, TAU);
+        self.eye_genes = self.eye_genes.map(EyeGenes::normalized);
+        self.body_genes = self.body_genes.map(BodyGenes::normalized);
+        self.speed_min = self.speed_min.max(0.0);
+        self.speed_max = self.speed_max.max(self.speed_min);
+        self.initial_speed = self.initial_speed.clamp(self.speed_min, self.speed_max);
+        self.speed_accel = self.speed_accel.max(0.0);
+        self.rotation_accel = self.rotation_accel.max(0.0);
+        self.hunts.retain(|species| *species < num_species);
+        self.hunts.sort();
+        self.hunts.dedup();
+        self.reach = self.reach.max(0.0);
+        self.body_radius = self.body_radius.max(0.0);
+        self.metabolism = self.metabolism.map(Metabolism::normalized);
+        self.hunger = self.hunger.map(Hunger::normalized);
+        self.novelty_weight = self.novelty_weight.clamp(0.0, 1.0);
+        self.population_dynamics = self.population_dynamics.normalized();
+        self
+    }
+
+    // Returns an eye of given shape for each of the channels
+    pub(crate) fn eyes(&self, shape: &EyeShape) -> Vec<Eye> {
+        self.channels
+            .iter()
+            .map(|channel| {
+                EyeChannel {
+                    fov_range: channel.fov_range * shape.range_scale,
+                    ..*channel
+                }
+                .eye(shape.fov_angle)
+            })
+            .collect()
+    }
+
+    // Shape of eyes encoded by given trait genes
+    pub(crate) fn eye_shape(&self, genes: &[f32]) -> EyeShape {
+        match &self.eye_genes {
+            Some(eye_genes) => eye_genes.express(genes),
+            None => EyeShape::fixed(self.fov_angle),
+        }
+    }
+
+    // Shape of body encoded by given trait genes
+    pub(crate) fn body_shape(&self, genes: &[f32]) -> BodyShape {
+        match &self.body_genes {
+            Some(body_genes) => body_genes.express(&genes[self.eye_genes_count()..]),
+            None => BodyShape::fixed(),
+        }
+    }
+
+    // Largest body size its agents may have, see `BodyGenes`
+    pub(crate) fn max_body_size(&self) -> f32 {
+        self.body_genes
+            .map_or(1.0, |body_genes| body_genes.max_size)
+    }
+
+    // Highest speed its agents may reach, see `BodyGenes`
+    pub(crate) fn max_agent_speed(&self) -> f32 {
+        let speed = self
+            .body_genes
+            .map_or(1.0, |body_genes| body_genes.max_speed);
+
+        (self.speed_max * speed).max(self.speed_min)
+    }
+
+    // Number of genes its agents have on top of brain weights: eye genes
+    // followed by body genes
+    pub(crate) fn trait_genes(&self) -> usize {
+        self.eye_genes_count() + self.body_genes.map_or(0, |_| BodyGenes::GENES)
+    }
+
+    fn eye_genes_count(&self) -> usize {
+        self.eye_genes.map_or(0, |_| EyeGenes::GENES)
+    }
+
+    // Number of brain inputs its agents have: whatever its eye channels
+    // see, followed by `Senses`
+    pub(crate) fn input_neurons(&self) -> usize {
+        self.channels
+            .iter()
+            .map(|channel| channel.inputs(self.eye_mode))
+            .sum::<usize>()
+            + self.senses.inputs()
+    }
+}
+
+// Prey and predators hunting them
+pub(crate) fn default_species() -> Vec<SpeciesConfig> {
+    vec![SpeciesConfig::prey(), SpeciesConfig::predator(0)]
+}
@@ -4,28 +4,86 @@ use std::fmt;
 #[derive(Clone, Debug)]
 pub struct Statistics {
     pub generation: usize,
-    pub prey_ga: ga::Statistics,
-    pub predator_ga: ga::Statistics,
-    pub num_dead_prey: u32,
-    pub num_dead_predators: u32,
+    // One per species, in order of `SimulationConfig::species`
+    pub species: Vec<SpeciesStatistics>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpeciesStatistics {
+    pub name: String,
+    pub ga: ga::Statistics,
+    pub num_alive: u32,
+    pub num_dead: u32,
+    pub morphology: MorphologyStatistics,
+}
+
+// Averages of the species' heritable traits, see `EyeGenes` and
+// `BodyGenes`
+#[derive(Clone, Debug, Default)]
+pub struct MorphologyStatistics {
+    pub fov_angle: f32,
+    pub fov_range_scale: f32,
+    pub body_size: f32,
+    pub body_speed: f32,
+    pub body_agility: f32,
+}
+
+impl Statistics {
+    pub fn species(&self, name: &str) -> Option<&SpeciesStatistics> {
+        self.species.iter().find(|species| species.name == name)
+    }
+}
+
+impl MorphologyStatistics {
+    pub(crate) fn new<'a>(agents: impl Iterator<Item = &'a Agent>) -> Self {
+        let mut stats = Self::default();
+        let mut count = 0;
+
+        for agent in agents {
+            stats.fov_angle += agent.eye_shape.fov_angle;
+            stats.fov_range_scale += agent.eye_shape.range_scale;
+            stats.body_size += agent.body_shape.size;
+            stats.body_speed += agent.body_shape.speed;
+            stats.body_agility += agent.body_shape.agility;
+            count += 1;
+        }
+
+        if count > 0 {
+            stats.fov_angle /= count as f32;
+            stats.fov_range_scale /= count as f32;
+            stats.body_size /= count as f32;
+            stats.body_speed /= count as f32;
+            stats.body_agility /= count as f32;
+        }
+
+        stats
+    }
 }
 
 impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "generation {}", self.generation)?;
+
+        for species in &self.species {
+            write!(f, ", {species}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SpeciesStatistics {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "generation {}, prey(min={:.2}, max={:.2}, avg={:.2}, median={:.2}, dead={}), predators(min={:.2}, max={:.2}, avg={:.2}, median={:.2}, dead={})",
-            self.generation,
-            self.prey_ga.min_fitness,
-            self.prey_ga.max_fitness,
-            self.prey_ga.avg_fitness,
-            self.prey_ga.median_fitness,
-            self.num_dead_prey,
-            self.predator_ga.min_fitness,
-            self.predator_ga.max_fitness,
-            self.predator_ga.avg_fitness,
-            self.predator_ga.median_fitness,
-            self.num_dead_predators,
+            "{}(min={:.2}, max={:.2}, avg={:.2}, median={:.2}, alive={}, dead={})",
+            self.name,
+            self.ga.min_fitness,
+            self.ga.max_fitness,
+            self.ga.avg_fitness,
+            self.ga.median_fitness,
+            self.num_alive,
+            self.num_dead,
         )
     }
 }
@@ -0,0 +1,182 @@
+use crate::*;
+
+const ARENA_CENTER: na::Point2<f32> = na::Point2::new(0.5, 0.5);
+const ARENA_RADIUS: f32 = 0.5;
+
+// Shape of the world - it decides what happens at its edges and so how far
+// apart things are
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Topology {
+    // The unit square, with opposite edges glued together - whatever
+    // leaves through one edge comes back through the other one
+    #[default]
+    Torus,
+    // The unit square, surrounded by walls everyone bounces off of
+    Box,
+    // Circle inscribed into the unit square, surrounded by a wall
+    Arena,
+}
+
+impl Topology {
+    // Shortest vector leading from `from` to `to`
+    pub fn displacement(&self, from: na::Point2<f32>, to: na::Point2<f32>) -> na::Vector2<f32> {
+        let vec = to - from;
+
+        match self {
+            Self::Torus => vec.map(|coord| coord - coord.round()),
+            Self::Box | Self::Arena => vec,
+        }
+    }
+
+    pub fn distance(&self, a: na::Point2<f32>, b: na::Point2<f32>) -> f32 {
+        self.displacement(a, b).norm()
+    }
+
+    // Where `to` appears to be when looked at from `from` - on a torus
+    // that's its closest copy, which might lie outside of the unit square
+    pub fn image(&self, from: na::Point2<f32>, to: na::Point2<f32>) -> na::Point2<f32> {
+        from + self.displacement(from, to)
+    }
+
+    pub fn contains(&self, position: na::Point2<f32>) -> bool {
+        match self {
+            Self::Torus | Self::Box => {
+                (0.0..=1.0).contains(&position.x) && (0.0..=1.0).contains(&position.y)
+            }
+            Self::Arena => na::distance(&position, &ARENA_CENTER) <= ARENA_RADIUS,
+        }
+    }
+
+    pub fn random_position(&self, rng: &mut dyn RngCore) -> na::Point2<f32> {
+        loop {
+            let position = rng.r#gen();
+
+            if self.contains(position) {
+                return position;
+            }
+        }
+    }
+
+    // Brings something that has just moved back into the world - either
+    // by wrapping it around or by bouncing it off the wall it ran into
+    pub fn confine(&self, position: &mut na::Point2<f32>, rotation: &mut na::Rotation2<f32>) {
+        match self {
+            Self::Torus => {
+                position.x = na::wrap(position.x, 0.0, 1.0);
+                position.y = na::wrap(position.y, 0.0, 1.0);
+            }
+
+            Self::Box => {
+                let mut heading = *rotation * na::Vector2::y();
+                let mut bounced = false;
+
+                for axis in 0..2 {
+                    if !(0.0..=1.0).contains(&position[axis]) {
+                        position[axis] = position[axis].clamp(0.0, 1.0);
+                        heading[axis] = -heading[axis];
+                        bounced = true;
+                    }
+                }
+
+                if bounced {
+                    *rotation = heading_rotation(heading);
+                }
+            }
+
+            Self::Arena => {
+                let offset = *position - ARENA_CENTER;
+                let distance = offset.norm();
+
+                if distance <= ARENA_RADIUS {
+                    return;
+                }
+
+                let normal = offset / distance;
+
+                *position = ARENA_CENTER + normal * ARENA_RADIUS;
+
+                bounce(rotation, -normal);
+            }
+        }
+    }
+}
+
+// Reflects the heading off of a surface with given normal (pointing towards
+// the side we're on), unless we're already moving away from it
+pub(crate) fn bounce(rotation: &mut na::Rotation2<f32>, normal: na::Vector2<f32>) {
+    let heading = *rotation * na::Vector2::y();
+    let dot = heading.dot(&normal);
+
+    if dot < 0.0 {
+        *rotation = heading_rotation(heading - 2.0 * dot * normal);
+    }
+}
+
+// Rotation that makes animals move along `heading` (they always move along
+// their rotated Y axis)
+fn heading_rotation(heading: na::Vector2<f32>) -> na::Rotation2<f32> {
+    na::Rotation2::new(f32::atan2(-heading.x, heading.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: na::Vector2<f32>, expected: na::Vector2<f32>) {
+        assert!(
+            (actual - expected).norm() < 1e-5,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn torus_distances_wrap_around() {
+        let a = na::Point2::new(0.95, 0.5);
+        let b = na::Point2::new(0.05, 0.5);
+
+        assert!((Topology::Torus.distance(a, b) - 0.1).abs() < 1e-5);
+        assert!((Topology::Box.distance(a, b) - 0.9).abs() < 1e-5);
+        assert_close(
+            Topology::Torus.image(a, b).coords,
+            na::Vector2::new(1.05, 0.5),
+        );
+    }
+
+    #[test]
+    fn torus_wraps_positions() {
+        let mut position = na::Point2::new(1.1, -0.2);
+        let mut rotation = na::Rotation2::new(1.0);
+
+        Topology::Torus.confine(&mut position, &mut rotation);
+
+        assert_close(position.coords, na::Vector2::new(0.1, 0.8));
+        assert_eq!(rotation.angle(), 1.0);
+    }
+
+    #[test]
+    fn box_bounces_off_walls() {
+        // Heading right (and a bit up), just past the right wall
+        let mut position = na::Point2::new(1.1, 0.5);
+        let mut rotation = heading_rotation(na::Vector2::new(1.0, 1.0));
+
+        Topology::Box.confine(&mut position, &mut rotation);
+
+        assert_close(position.coords, na::Vector2::new(1.0, 0.5));
+        assert_close(
+            rotation * na::Vector2::y(),
+            na::Vector2::new(-1.0, 1.0).normalize(),
+        );
+    }
+
+    #[test]
+    fn arena_bounces_off_walls() {
+        let mut position = na::Point2::new(0.5, 1.2);
+        let mut rotation = heading_rotation(na::Vector2::y());
+
+        Topology::Arena.confine(&mut position, &mut rotation);
+
+        assert_close(position.coords, na::Vector2::new(0.5, 1.0));
+        assert_close(rotation * na::Vector2::y(), -na::Vector2::y());
+        assert!(!Topology::Arena.contains(na::Point2::new(0.05, 0.05)));
+    }
+}
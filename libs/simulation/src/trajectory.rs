@@ -0,0 +1,47 @@
+use crate::*;
+
+const TURN_BINS: usize = 4;
+
+// Keeps track of how an animal moved throughout its life, for novelty
+// search - see `ga::NoveltyArchive`
+#[derive(Clone, Debug, Default)]
+pub struct Trajectory {
+    path_length: f32,
+    // How many times the animal turned hard left, left, right and hard
+    // right
+    turns: [u32; TURN_BINS],
+}
+
+impl Trajectory {
+    pub fn path_length(&self) -> f32 {
+        self.path_length
+    }
+
+    // `turn` is the rotation applied in this step, relative to the largest
+    // one possible (i.e. within `-1.0..=1.0`)
+    pub(crate) fn record(&mut self, distance: f32, turn: f32) {
+        let bin = ((turn.clamp(-1.0, 1.0) + 1.0) / 2.0 * TURN_BINS as f32) as usize;
+
+        self.path_length += distance;
+        self.turns[bin.min(TURN_BINS - 1)] += 1;
+    }
+
+    // Final position, path length (relative to `max_path_length`) and the
+    // turn histogram, all within `0.0..=1.0`
+    pub(crate) fn behaviour(
+        &self,
+        position: na::Point2<f32>,
+        max_path_length: f32,
+    ) -> ga::Behaviour {
+        let steps = self.turns.iter().sum::<u32>().max(1) as f32;
+
+        let mut features = vec![
+            position.x,
+            position.y,
+            (self.path_length / max_path_length).min(1.0),
+        ];
+
+        features.extend(self.turns.iter().map(|&turns| turns as f32 / steps));
+        ga::Behaviour::new(features)
+    }
+}
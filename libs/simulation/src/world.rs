@@ -4,27 +4,48 @@ pub const PREY_COUNT: usize = 40;
 pub const PREDATOR_COUNT: usize = 6;
 pub const FOOD_COUNT: usize = 60;
 
+// How many objects each cell of the spatial indices holds on average
+const GRID_DENSITY: f32 = 4.0;
+
 #[derive(Debug)]
 pub struct World {
-    pub(crate) animals: Vec<Animal>,
-    pub(crate) predators: Vec<Predator>,
+    // Everyone, of all species
+    pub(crate) agents: Vec<Agent>,
     pub(crate) foods: Vec<Food>,
+    pub(crate) obstacles: Vec<Obstacle>,
+    // Centers of food patches, see `FoodSpawning::Patches`
+    pub(crate) food_patches: Vec<na::Point2<f32>>,
+    // Spatial indices of the above (one per species for agents), see
+    // `World::rebuild_index()`
+    pub(crate) agent_grids: Vec<SpatialGrid>,
+    pub(crate) food_grid: SpatialGrid,
 }
 
 impl World {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        Self::random_with_counts(rng, PREY_COUNT, PREDATOR_COUNT, FOOD_COUNT)
+        Self::random_with_config(rng, &SimulationConfig::default().normalized())
     }
 
-    pub fn random_with_counts(
-        rng: &mut dyn RngCore,
-        prey_count: usize,
-        predator_count: usize,
-        food_count: usize,
-    ) -> Self {
-        let animals = (0..prey_count).map(|_| Animal::random(rng)).collect();
-        let predators = (0..predator_count).map(|_| Predator::random(rng)).collect();
-        let foods = (0..food_count).map(|_| Food::random(rng)).collect();
+    // Expects `cfg` to be normalized
+    pub(crate) fn random_with_config(rng: &mut dyn RngCore, cfg: &SimulationConfig) -> Self {
+        let agents = (0..cfg.species.len())
+            .flat_map(|species| Self::random_agents(rng, cfg, species))
+            .collect();
+
+        let mut world = Self {
+            agents,
+            foods: Vec::new(),
+            obstacles: cfg.obstacles.clone(),
+            food_patches: Vec::new(),
+            agent_grids: vec![SpatialGrid::new(1); cfg.species.len()],
+            food_grid: SpatialGrid::new(1),
+        };
+
+        world.food_patches = cfg.food_spawning.patches(rng, &cfg.obstacles, cfg.topology);
+
+        world.foods = (0..cfg.food_count)
+            .map(|_| world.spawn_food(rng, cfg))
+            .collect();
 
         // ^ Our algorithm allows for animals and foods to overlap, so
         // | it's hardly ideal - but good enough for our purposes.
@@ -35,22 +56,98 @@ impl World {
         // | https://en.wikipedia.org/wiki/Supersampling
         // ---
 
-        Self {
-            animals,
-            predators,
-            foods,
+        world.place_within(rng, cfg.topology);
+        world.rebuild_index();
+        world
+    }
+
+    pub(crate) fn random_agents(
+        rng: &mut dyn RngCore,
+        cfg: &SimulationConfig,
+        species: usize,
+    ) -> Vec<Agent> {
+        (0..cfg.species[species].count)
+            .map(|_| Agent::random(rng, cfg, species))
+            .collect()
+    }
+
+    // Brings spatial indices up to date with everyone's current positions;
+    // the dead are left out
+    pub(crate) fn rebuild_index(&mut self) {
+        for (species, grid) in self.agent_grids.iter_mut().enumerate() {
+            let agents: Vec<_> = self
+                .agents
+                .iter()
+                .enumerate()
+                .filter(|(_, agent)| agent.alive && agent.species == species)
+                .map(|(idx, agent)| (idx, agent.position))
+                .collect();
+
+            grid.fit(agents.len(), GRID_DENSITY);
+            grid.rebuild(agents);
         }
+
+        let foods: Vec<_> = self
+            .foods
+            .iter()
+            .enumerate()
+            .filter(|(_, food)| food.is_available())
+            .map(|(idx, food)| (idx, food.position))
+            .collect();
+
+        self.food_grid.fit(foods.len(), GRID_DENSITY);
+        self.food_grid.rebuild(foods);
     }
 
-    pub fn animals(&self) -> &[Animal] {
-        &self.animals
+    // Moves everything that lies outside of given topology (e.g. in the
+    // corners of `Topology::Arena`) or inside an obstacle to some random
+    // place within it
+    pub(crate) fn place_within(&mut self, rng: &mut dyn RngCore, topology: Topology) {
+        let positions = self
+            .agents
+            .iter_mut()
+            .map(|agent| &mut agent.position)
+            .chain(self.foods.iter_mut().map(|food| &mut food.position));
+
+        for position in positions {
+            if !is_free(&self.obstacles, topology, *position) {
+                *position = random_free_position(rng, &self.obstacles, topology);
+            }
+        }
+    }
+
+    // Grows a new piece of food, of random type, somewhere it's supposed to
+    // grow
+    pub(crate) fn spawn_food(&self, rng: &mut dyn RngCore, cfg: &SimulationConfig) -> Food {
+        let kind = FoodType::pick(rng, &cfg.food_types);
+
+        let position =
+            cfg.food_spawning
+                .position(rng, &self.food_patches, &self.obstacles, cfg.topology);
+
+        Food::new(position, kind, &cfg.food_types[kind])
     }
 
-    pub fn predators(&self) -> &[Predator] {
-        &self.predators
+    pub fn agents(&self) -> &[Agent] {
+        &self.agents
+    }
+
+    // Agents of given species, see `SimulationConfig::species`
+    pub fn agents_of(&self, species: usize) -> impl Iterator<Item = &Agent> {
+        self.agents
+            .iter()
+            .filter(move |agent| agent.species == species)
     }
 
     pub fn foods(&self) -> &[Food] {
         &self.foods
     }
+
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    pub fn food_patches(&self) -> &[na::Point2<f32>] {
+        &self.food_patches
+    }
 }